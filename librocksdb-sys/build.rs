@@ -35,7 +35,8 @@ fn rocksdb_include_dir() -> String {
 
 fn bindgen_rocksdb() {
     let bindings = bindgen::Builder::default()
-        .header(rocksdb_include_dir() + "/rocksdb/c.h")
+        .header("rocksdb_ext.h")
+        .clang_arg(format!("-I{}", rocksdb_include_dir()))
         .derive_debug(false)
         .blacklist_type("max_align_t") // https://github.com/rust-lang-nursery/rust-bindgen/issues/550
         .ctypes_prefix("libc")
@@ -183,6 +184,26 @@ fn build_rocksdb() {
     config.compile("librocksdb.a");
 }
 
+fn build_rocksdb_ext() {
+    let target = env::var("TARGET").unwrap();
+
+    let mut config = cc::Build::new();
+    config.include(rocksdb_include_dir());
+    config.include(".");
+    config.define("NDEBUG", Some("1"));
+
+    if target.contains("msvc") {
+        config.flag("-EHsc");
+    } else {
+        config.flag(&cxx_standard());
+        config.flag("-Wno-unused-parameter");
+    }
+
+    config.file("rocksdb_ext.cc");
+    config.cpp(true);
+    config.compile("librocksdb_ext.a");
+}
+
 fn build_snappy() {
     let target = env::var("TARGET").unwrap();
     let endianness = env::var("CARGO_CFG_TARGET_ENDIAN").unwrap();
@@ -327,8 +348,15 @@ fn cxx_standard() -> String {
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
+    println!("cargo:rerun-if-changed=rocksdb_ext.h");
+    println!("cargo:rerun-if-changed=rocksdb_ext.cc");
+
     bindgen_rocksdb();
 
+    // The extension functions are compiled against the RocksDB headers in use
+    // (bundled or from `ROCKSDB_INCLUDE_DIR`) and must be linked before it.
+    build_rocksdb_ext();
+
     if !try_to_find_and_link_lib("ROCKSDB") {
         println!("cargo:rerun-if-changed=rocksdb/");
         fail_on_empty_directory("rocksdb");
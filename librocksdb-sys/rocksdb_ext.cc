@@ -0,0 +1,874 @@
+#include "rocksdb_ext.h"
+
+#include <cassert>
+#include <cstdarg>
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <deque>
+#include <memory>
+#include <string>
+#include <unordered_map>
+#include <utility>
+#include <vector>
+
+#include "rocksdb/cache.h"
+#include "rocksdb/db.h"
+#include "rocksdb/env.h"
+#include "rocksdb/iostats_context.h"
+#include "rocksdb/listener.h"
+#include "rocksdb/merge_operator.h"
+#include "rocksdb/options.h"
+#include "rocksdb/statistics.h"
+#include "rocksdb/transaction_log.h"
+#include "rocksdb/utilities/db_ttl.h"
+#include "rocksdb/utilities/options_util.h"
+#include "rocksdb/write_buffer_manager.h"
+#include "rocksdb/write_batch.h"
+
+using rocksdb::Cache;
+using rocksdb::ColumnFamilyDescriptor;
+using rocksdb::ColumnFamilyHandle;
+using rocksdb::BackgroundErrorReason;
+using rocksdb::ColumnFamilyOptions;
+using rocksdb::CompactionJobInfo;
+using rocksdb::CompactionOptionsFIFO;
+using rocksdb::DB;
+using rocksdb::DBOptions;
+using rocksdb::DBWithTTL;
+using rocksdb::DbPath;
+using rocksdb::Env;
+using rocksdb::EventListener;
+using rocksdb::FlushJobInfo;
+using rocksdb::FlushOptions;
+using rocksdb::HistogramData;
+using rocksdb::Histograms;
+using rocksdb::HistogramsNameMap;
+using rocksdb::IOStatsContext;
+using rocksdb::InfoLogLevel;
+using rocksdb::Iterator;
+using rocksdb::LiveFileMetaData;
+using rocksdb::Logger;
+using rocksdb::MergeOperator;
+using rocksdb::Options;
+using rocksdb::Range;
+using rocksdb::ReadOptions;
+using rocksdb::Slice;
+using rocksdb::Snapshot;
+using rocksdb::Statistics;
+using rocksdb::Status;
+using rocksdb::Tickers;
+using rocksdb::TickersNameMap;
+using rocksdb::TransactionLogIterator;
+using rocksdb::WriteBatch;
+using rocksdb::WriteBufferManager;
+using rocksdb::WriteOptions;
+using rocksdb::WriteStallInfo;
+
+// These must stay layout compatible with the definitions in rocksdb/db/c.cc.
+struct rocksdb_t {
+  DB* rep;
+};
+struct rocksdb_iterator_t {
+  Iterator* rep;
+};
+struct rocksdb_column_family_handle_t {
+  ColumnFamilyHandle* rep;
+};
+struct rocksdb_snapshot_t {
+  const Snapshot* rep;
+};
+struct rocksdb_readoptions_t {
+  ReadOptions rep;
+  // stack variables to set pointers to in ReadOptions
+  Slice upper_bound;
+  Slice lower_bound;
+};
+struct rocksdb_writebatch_t {
+  WriteBatch rep;
+};
+struct rocksdb_writeoptions_t {
+  WriteOptions rep;
+};
+struct rocksdb_options_t {
+  Options rep;
+};
+struct rocksdb_flushoptions_t {
+  FlushOptions rep;
+};
+struct rocksdb_livefiles_t {
+  std::vector<LiveFileMetaData> rep;
+};
+struct rocksdb_fifo_compaction_options_t {
+  CompactionOptionsFIFO rep;
+};
+struct rocksdb_cache_t {
+  std::shared_ptr<Cache> rep;
+};
+struct rocksdb_wal_readoptions_t {
+  TransactionLogIterator::ReadOptions rep;
+};
+
+struct rocksdb_ext_write_buffer_manager_t {
+  std::shared_ptr<WriteBufferManager> rep;
+};
+
+// Forwards RocksDB events to C callbacks.
+struct rocksdb_ext_eventlistener_impl_t : public EventListener {
+  void* state_;
+  void (*destructor_)(void*);
+  void (*on_flush_completed_)(void*, const rocksdb_ext_flush_job_info_t*);
+  void (*on_compaction_completed_)(void*,
+                                   const rocksdb_ext_compaction_job_info_t*);
+  void (*on_stall_conditions_changed_)(void*,
+                                       const rocksdb_ext_write_stall_info_t*);
+  void (*on_background_error_)(void*, int, const char*);
+
+  ~rocksdb_ext_eventlistener_impl_t() override { (*destructor_)(state_); }
+
+  void OnFlushCompleted(DB* /*db*/, const FlushJobInfo& info) override {
+    rocksdb_ext_flush_job_info_t c_info;
+    c_info.cf_name = info.cf_name.data();
+    c_info.cf_name_len = info.cf_name.size();
+    c_info.file_path = info.file_path.data();
+    c_info.file_path_len = info.file_path.size();
+    c_info.job_id = info.job_id;
+    c_info.triggered_writes_slowdown = info.triggered_writes_slowdown;
+    c_info.triggered_writes_stop = info.triggered_writes_stop;
+    c_info.smallest_seqno = info.smallest_seqno;
+    c_info.largest_seqno = info.largest_seqno;
+    (*on_flush_completed_)(state_, &c_info);
+  }
+
+  void OnCompactionCompleted(DB* /*db*/,
+                             const CompactionJobInfo& info) override {
+    const std::string status = info.status.ToString();
+    rocksdb_ext_compaction_job_info_t c_info;
+    c_info.cf_name = info.cf_name.data();
+    c_info.cf_name_len = info.cf_name.size();
+    c_info.status = info.status.ok() ? nullptr : status.c_str();
+    c_info.job_id = info.job_id;
+    c_info.base_input_level = info.base_input_level;
+    c_info.output_level = info.output_level;
+    c_info.elapsed_micros = info.stats.elapsed_micros;
+    c_info.num_input_files = info.stats.num_input_files;
+    c_info.num_output_files = info.stats.num_output_files;
+    c_info.num_input_records = info.stats.num_input_records;
+    c_info.num_output_records = info.stats.num_output_records;
+    c_info.total_input_bytes = info.stats.total_input_bytes;
+    c_info.total_output_bytes = info.stats.total_output_bytes;
+    (*on_compaction_completed_)(state_, &c_info);
+  }
+
+  void OnStallConditionsChanged(const WriteStallInfo& info) override {
+    rocksdb_ext_write_stall_info_t c_info;
+    c_info.cf_name = info.cf_name.data();
+    c_info.cf_name_len = info.cf_name.size();
+    c_info.cur_condition = static_cast<int>(info.condition.cur);
+    c_info.prev_condition = static_cast<int>(info.condition.prev);
+    (*on_stall_conditions_changed_)(state_, &c_info);
+  }
+
+  void OnBackgroundError(BackgroundErrorReason reason,
+                         Status* bg_error) override {
+    const std::string error = bg_error->ToString();
+    (*on_background_error_)(state_, static_cast<int>(reason), error.c_str());
+  }
+};
+
+struct rocksdb_ext_eventlistener_t {
+  std::shared_ptr<rocksdb_ext_eventlistener_impl_t> rep;
+};
+
+// Formats log lines and forwards them to a C callback.
+struct rocksdb_ext_logger_impl_t : public Logger {
+  void* state_;
+  void (*destructor_)(void*);
+  void (*log_)(void*, int, const char*, size_t);
+
+  explicit rocksdb_ext_logger_impl_t(InfoLogLevel log_level)
+      : Logger(log_level) {}
+
+  ~rocksdb_ext_logger_impl_t() override { (*destructor_)(state_); }
+
+  using Logger::Logv;
+
+  void Logv(const char* format, va_list ap) override {
+    Logv(InfoLogLevel::INFO_LEVEL, format, ap);
+  }
+
+  void Logv(const InfoLogLevel log_level, const char* format,
+            va_list ap) override {
+    if (log_level < GetInfoLogLevel()) {
+      return;
+    }
+    char buf[512];
+    va_list backup_ap;
+    va_copy(backup_ap, ap);
+    int n = vsnprintf(buf, sizeof(buf), format, ap);
+    if (n < 0) {
+      va_end(backup_ap);
+      return;
+    }
+    if (static_cast<size_t>(n) < sizeof(buf)) {
+      va_end(backup_ap);
+      (*log_)(state_, log_level, buf, n);
+      return;
+    }
+    std::string msg(n + 1, '\0');
+    vsnprintf(&msg[0], msg.size(), format, backup_ap);
+    va_end(backup_ap);
+    (*log_)(state_, log_level, msg.data(), n);
+  }
+};
+
+struct rocksdb_ext_logger_t {
+  std::shared_ptr<rocksdb_ext_logger_impl_t> rep;
+};
+
+// Same as rocksdb_mergeoperator_t in rocksdb/db/c.cc, but merges can return
+// one of their inputs instead of a new value, and AllowSingleOperand is
+// configurable.
+struct rocksdb_ext_mergeoperator_impl_t : public MergeOperator {
+  void* state_;
+  void (*destructor_)(void*);
+  const char* (*name_)(void*);
+  char* (*full_merge_)(void*, const char*, size_t, const char*, size_t,
+                       const char* const*, const size_t*, int,
+                       unsigned char*, const char**, size_t*);
+  char* (*partial_merge_)(void*, const char*, size_t, const char* const*,
+                          const size_t*, int, unsigned char*, const char**,
+                          size_t*);
+  void (*delete_value_)(void*, const char*, size_t);
+  bool allow_single_operand_;
+
+  ~rocksdb_ext_mergeoperator_impl_t() override { (*destructor_)(state_); }
+
+  const char* Name() const override { return (*name_)(state_); }
+
+  bool AllowSingleOperand() const override { return allow_single_operand_; }
+
+  bool FullMergeV2(const MergeOperationInput& merge_in,
+                   MergeOperationOutput* merge_out) const override {
+    size_t n = merge_in.operand_list.size();
+    std::vector<const char*> operand_pointers(n);
+    std::vector<size_t> operand_sizes(n);
+    for (size_t i = 0; i < n; i++) {
+      operand_pointers[i] = merge_in.operand_list[i].data();
+      operand_sizes[i] = merge_in.operand_list[i].size();
+    }
+    const char* existing_value_data = nullptr;
+    size_t existing_value_len = 0;
+    if (merge_in.existing_value != nullptr) {
+      existing_value_data = merge_in.existing_value->data();
+      existing_value_len = merge_in.existing_value->size();
+    }
+    unsigned char success = 0;
+    const char* borrowed_value = nullptr;
+    size_t new_value_len = 0;
+    char* new_value = (*full_merge_)(
+        state_, merge_in.key.data(), merge_in.key.size(), existing_value_data,
+        existing_value_len, operand_pointers.data(), operand_sizes.data(),
+        static_cast<int>(n), &success, &borrowed_value, &new_value_len);
+    if (success && borrowed_value != nullptr) {
+      // The inputs outlive the merge, RocksDB copies the value from there.
+      merge_out->existing_operand = Slice(borrowed_value, new_value_len);
+    } else if (success) {
+      merge_out->new_value.assign(new_value, new_value_len);
+    }
+    if (new_value != nullptr) {
+      (*delete_value_)(state_, new_value, new_value_len);
+    }
+    return success;
+  }
+
+  bool PartialMergeMulti(const Slice& key, const std::deque<Slice>& operand_list,
+                         std::string* new_value,
+                         Logger* /*logger*/) const override {
+    if (partial_merge_ == nullptr) {
+      return false;
+    }
+    size_t n = operand_list.size();
+    std::vector<const char*> operand_pointers(n);
+    std::vector<size_t> operand_sizes(n);
+    for (size_t i = 0; i < n; i++) {
+      operand_pointers[i] = operand_list[i].data();
+      operand_sizes[i] = operand_list[i].size();
+    }
+    unsigned char success = 0;
+    const char* borrowed_value = nullptr;
+    size_t new_value_len = 0;
+    char* tmp_new_value = (*partial_merge_)(
+        state_, key.data(), key.size(), operand_pointers.data(),
+        operand_sizes.data(), static_cast<int>(n), &success, &borrowed_value,
+        &new_value_len);
+    if (success) {
+      new_value->assign(borrowed_value != nullptr ? borrowed_value
+                                                  : tmp_new_value,
+                        new_value_len);
+    }
+    if (tmp_new_value != nullptr) {
+      (*delete_value_)(state_, tmp_new_value, new_value_len);
+    }
+    return success;
+  }
+};
+
+// Same as SaveError in rocksdb/db/c.cc.
+static bool SaveError(char** errptr, const Status& s) {
+  assert(errptr != nullptr);
+  if (s.ok()) {
+    return false;
+  } else if (*errptr == nullptr) {
+    *errptr = strdup(s.ToString().c_str());
+  } else {
+    free(*errptr);
+    *errptr = strdup(s.ToString().c_str());
+  }
+  return true;
+}
+
+// Tickers and histograms are looked up by name, as their numeric values
+// change between RocksDB releases.
+template <typename T>
+static bool LookupByName(const std::vector<std::pair<T, std::string>>& map,
+                         const char* name, size_t name_len, T* out) {
+  const std::string n(name, name_len);
+  for (const auto& entry : map) {
+    if (entry.second == n) {
+      *out = entry.first;
+      return true;
+    }
+  }
+  return false;
+}
+
+extern "C" {
+
+void rocksdb_ext_options_set_manual_wal_flush(rocksdb_options_t* opt,
+                                              unsigned char v) {
+  opt->rep.manual_wal_flush = v;
+}
+
+void rocksdb_ext_options_set_wal_bytes_per_sync(rocksdb_options_t* opt,
+                                                uint64_t v) {
+  opt->rep.wal_bytes_per_sync = v;
+}
+
+void rocksdb_ext_options_set_memtable_whole_key_filtering(
+    rocksdb_options_t* opt, unsigned char v) {
+  opt->rep.memtable_whole_key_filtering = v;
+}
+
+void rocksdb_ext_options_set_row_cache(rocksdb_options_t* opt,
+                                       rocksdb_cache_t* cache) {
+  opt->rep.row_cache = cache->rep;
+}
+
+void rocksdb_ext_options_set_skip_checking_sst_file_sizes_on_db_open(
+    rocksdb_options_t* opt, unsigned char v) {
+  opt->rep.skip_checking_sst_file_sizes_on_db_open = v;
+}
+
+void rocksdb_ext_options_set_stats_persist_period_sec(rocksdb_options_t* opt,
+                                                      unsigned int v) {
+  opt->rep.stats_persist_period_sec = v;
+}
+
+static std::vector<DbPath> DbPaths(const char* const* paths,
+                                   const uint64_t* target_sizes,
+                                   size_t num_paths) {
+  std::vector<DbPath> db_paths;
+  for (size_t i = 0; i < num_paths; i++) {
+    db_paths.emplace_back(paths[i], target_sizes[i]);
+  }
+  return db_paths;
+}
+
+void rocksdb_ext_options_set_db_paths(rocksdb_options_t* opt,
+                                      const char* const* paths,
+                                      const uint64_t* target_sizes,
+                                      size_t num_paths) {
+  opt->rep.db_paths = DbPaths(paths, target_sizes, num_paths);
+}
+
+void rocksdb_ext_options_set_cf_paths(rocksdb_options_t* opt,
+                                      const char* const* paths,
+                                      const uint64_t* target_sizes,
+                                      size_t num_paths) {
+  opt->rep.cf_paths = DbPaths(paths, target_sizes, num_paths);
+}
+
+rocksdb_ext_write_buffer_manager_t* rocksdb_ext_write_buffer_manager_create(
+    size_t buffer_size) {
+  rocksdb_ext_write_buffer_manager_t* wbm =
+      new rocksdb_ext_write_buffer_manager_t;
+  wbm->rep = std::make_shared<WriteBufferManager>(buffer_size);
+  return wbm;
+}
+
+rocksdb_ext_write_buffer_manager_t*
+rocksdb_ext_write_buffer_manager_create_with_cache(size_t buffer_size,
+                                                   rocksdb_cache_t* cache) {
+  rocksdb_ext_write_buffer_manager_t* wbm =
+      new rocksdb_ext_write_buffer_manager_t;
+  wbm->rep = std::make_shared<WriteBufferManager>(buffer_size, cache->rep);
+  return wbm;
+}
+
+void rocksdb_ext_write_buffer_manager_destroy(
+    rocksdb_ext_write_buffer_manager_t* wbm) {
+  delete wbm;
+}
+
+size_t rocksdb_ext_write_buffer_manager_memory_usage(
+    rocksdb_ext_write_buffer_manager_t* wbm) {
+  return wbm->rep->memory_usage();
+}
+
+size_t rocksdb_ext_write_buffer_manager_buffer_size(
+    rocksdb_ext_write_buffer_manager_t* wbm) {
+  return wbm->rep->buffer_size();
+}
+
+void rocksdb_ext_options_set_write_buffer_manager(
+    rocksdb_options_t* opt, rocksdb_ext_write_buffer_manager_t* wbm) {
+  opt->rep.write_buffer_manager = wbm->rep;
+}
+
+void rocksdb_ext_fifo_compaction_options_set_allow_compaction(
+    rocksdb_fifo_compaction_options_t* fifo_opts, unsigned char v) {
+  fifo_opts->rep.allow_compaction = v;
+}
+
+rocksdb_ext_eventlistener_t* rocksdb_ext_eventlistener_create(
+    void* state, void (*destructor)(void*),
+    void (*on_flush_completed)(void*, const rocksdb_ext_flush_job_info_t*),
+    void (*on_compaction_completed)(void*,
+                                    const rocksdb_ext_compaction_job_info_t*),
+    void (*on_stall_conditions_changed)(void*,
+                                        const rocksdb_ext_write_stall_info_t*),
+    void (*on_background_error)(void*, int reason, const char* error)) {
+  auto impl = std::make_shared<rocksdb_ext_eventlistener_impl_t>();
+  impl->state_ = state;
+  impl->destructor_ = destructor;
+  impl->on_flush_completed_ = on_flush_completed;
+  impl->on_compaction_completed_ = on_compaction_completed;
+  impl->on_stall_conditions_changed_ = on_stall_conditions_changed;
+  impl->on_background_error_ = on_background_error;
+  rocksdb_ext_eventlistener_t* listener = new rocksdb_ext_eventlistener_t;
+  listener->rep = impl;
+  return listener;
+}
+
+void rocksdb_ext_options_add_eventlistener(
+    rocksdb_options_t* opt, rocksdb_ext_eventlistener_t* listener) {
+  opt->rep.listeners.push_back(listener->rep);
+}
+
+void rocksdb_ext_eventlistener_destroy(rocksdb_ext_eventlistener_t* listener) {
+  delete listener;
+}
+
+rocksdb_ext_logger_t* rocksdb_ext_logger_create(
+    void* state, void (*destructor)(void*), int log_level,
+    void (*log)(void*, int level, const char* msg, size_t msg_len)) {
+  auto impl = std::make_shared<rocksdb_ext_logger_impl_t>(
+      static_cast<InfoLogLevel>(log_level));
+  impl->state_ = state;
+  impl->destructor_ = destructor;
+  impl->log_ = log;
+  rocksdb_ext_logger_t* logger = new rocksdb_ext_logger_t;
+  logger->rep = impl;
+  return logger;
+}
+
+void rocksdb_ext_options_set_info_log(rocksdb_options_t* opt,
+                                      rocksdb_ext_logger_t* logger) {
+  opt->rep.info_log = logger->rep;
+}
+
+void rocksdb_ext_logger_destroy(rocksdb_ext_logger_t* logger) {
+  delete logger;
+}
+
+void rocksdb_ext_options_set_merge_operator(
+    rocksdb_options_t* opt, void* state, void (*destructor)(void*),
+    const char* (*name)(void*),
+    char* (*full_merge)(void*, const char* key, size_t key_length,
+                        const char* existing_value,
+                        size_t existing_value_length,
+                        const char* const* operands_list,
+                        const size_t* operands_list_length, int num_operands,
+                        unsigned char* success, const char** borrowed_value,
+                        size_t* new_value_length),
+    char* (*partial_merge)(void*, const char* key, size_t key_length,
+                           const char* const* operands_list,
+                           const size_t* operands_list_length,
+                           int num_operands, unsigned char* success,
+                           const char** borrowed_value,
+                           size_t* new_value_length),
+    void (*delete_value)(void*, const char* value, size_t value_length),
+    unsigned char allow_single_operand) {
+  auto impl = std::make_shared<rocksdb_ext_mergeoperator_impl_t>();
+  impl->state_ = state;
+  impl->destructor_ = destructor;
+  impl->name_ = name;
+  impl->full_merge_ = full_merge;
+  impl->partial_merge_ = partial_merge;
+  impl->delete_value_ = delete_value;
+  impl->allow_single_operand_ = allow_single_operand;
+  opt->rep.merge_operator = impl;
+}
+
+void rocksdb_ext_load_latest_options(
+    const char* path, unsigned char ignore_unknown_options,
+    rocksdb_options_t** db_options, size_t* num_column_families,
+    char*** column_family_names, rocksdb_options_t*** column_family_options,
+    char** errptr) {
+  DBOptions db_opt;
+  std::vector<ColumnFamilyDescriptor> cf_descs;
+  if (SaveError(errptr, rocksdb::LoadLatestOptions(
+                            std::string(path), Env::Default(), &db_opt,
+                            &cf_descs, ignore_unknown_options))) {
+    return;
+  }
+
+  rocksdb_options_t* result = new rocksdb_options_t;
+  result->rep = Options(db_opt, ColumnFamilyOptions());
+  *db_options = result;
+
+  const size_t n = cf_descs.size();
+  char** names = static_cast<char**>(malloc(n * sizeof(char*)));
+  rocksdb_options_t** cf_options =
+      static_cast<rocksdb_options_t**>(malloc(n * sizeof(rocksdb_options_t*)));
+  for (size_t i = 0; i < n; i++) {
+    names[i] = strdup(cf_descs[i].name.c_str());
+    cf_options[i] = new rocksdb_options_t;
+    cf_options[i]->rep = Options(DBOptions(), cf_descs[i].options);
+  }
+  *num_column_families = n;
+  *column_family_names = names;
+  *column_family_options = cf_options;
+}
+
+void rocksdb_ext_load_latest_options_destroy(
+    char** column_family_names, rocksdb_options_t** column_family_options,
+    size_t num_column_families) {
+  for (size_t i = 0; i < num_column_families; i++) {
+    free(column_family_names[i]);
+  }
+  free(column_family_names);
+  free(column_family_options);
+}
+
+uint64_t rocksdb_ext_options_statistics_get_ticker_count(
+    rocksdb_options_t* opt, const char* name, size_t name_len) {
+  Statistics* statistics = opt->rep.statistics.get();
+  Tickers ticker;
+  if (statistics == nullptr ||
+      !LookupByName(TickersNameMap, name, name_len, &ticker)) {
+    return 0;
+  }
+  return statistics->getTickerCount(ticker);
+}
+
+unsigned char rocksdb_ext_options_statistics_get_histogram_data(
+    rocksdb_options_t* opt, const char* name, size_t name_len,
+    rocksdb_ext_histogram_data_t* data) {
+  Statistics* statistics = opt->rep.statistics.get();
+  Histograms histogram;
+  if (statistics == nullptr ||
+      !LookupByName(HistogramsNameMap, name, name_len, &histogram)) {
+    return 0;
+  }
+  HistogramData hist;
+  statistics->histogramData(histogram, &hist);
+  data->median = hist.median;
+  data->percentile95 = hist.percentile95;
+  data->percentile99 = hist.percentile99;
+  data->average = hist.average;
+  data->standard_deviation = hist.standard_deviation;
+  data->max = hist.max;
+  data->count = hist.count;
+  data->sum = hist.sum;
+  data->min = hist.min;
+  return 1;
+}
+
+void rocksdb_ext_iostats_context_reset() {
+  rocksdb::get_iostats_context()->Reset();
+}
+
+void rocksdb_ext_iostats_context_get(rocksdb_ext_iostats_t* stats) {
+  const IOStatsContext* ctx = rocksdb::get_iostats_context();
+  stats->bytes_written = ctx->bytes_written;
+  stats->bytes_read = ctx->bytes_read;
+  stats->open_nanos = ctx->open_nanos;
+  stats->allocate_nanos = ctx->allocate_nanos;
+  stats->write_nanos = ctx->write_nanos;
+  stats->read_nanos = ctx->read_nanos;
+  stats->range_sync_nanos = ctx->range_sync_nanos;
+  stats->fsync_nanos = ctx->fsync_nanos;
+  stats->prepare_write_nanos = ctx->prepare_write_nanos;
+  stats->logger_nanos = ctx->logger_nanos;
+}
+
+void rocksdb_ext_flush_cfs(
+    rocksdb_t* db, const rocksdb_flushoptions_t* options,
+    rocksdb_column_family_handle_t** column_families, int num_column_families,
+    char** errptr) {
+  std::vector<ColumnFamilyHandle*> handles(num_column_families);
+  for (int i = 0; i < num_column_families; i++) {
+    handles[i] = column_families[i]->rep;
+  }
+  SaveError(errptr, db->rep->Flush(options->rep, handles));
+}
+
+void rocksdb_ext_flush_wal(rocksdb_t* db, unsigned char sync,
+                           char** errptr) {
+  SaveError(errptr, db->rep->FlushWAL(sync));
+}
+
+void rocksdb_ext_sync_wal(rocksdb_t* db, char** errptr) {
+  SaveError(errptr, db->rep->SyncWAL());
+}
+
+void rocksdb_ext_pause_background_work(rocksdb_t* db, char** errptr) {
+  SaveError(errptr, db->rep->PauseBackgroundWork());
+}
+
+void rocksdb_ext_continue_background_work(rocksdb_t* db, char** errptr) {
+  SaveError(errptr, db->rep->ContinueBackgroundWork());
+}
+
+void rocksdb_ext_approximate_memtable_stats_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family,
+    const char* start_key, size_t start_key_len, const char* limit_key,
+    size_t limit_key_len, uint64_t* count, uint64_t* size) {
+  ColumnFamilyHandle* cf = column_family == nullptr
+                               ? db->rep->DefaultColumnFamily()
+                               : column_family->rep;
+  Range range(Slice(start_key, start_key_len), Slice(limit_key, limit_key_len));
+  db->rep->GetApproximateMemTableStats(cf, range, count, size);
+}
+
+rocksdb_t* rocksdb_ext_open_column_families_with_ttl(
+    const rocksdb_options_t* db_options, const char* name,
+    int num_column_families, const char** column_family_names,
+    const rocksdb_options_t** column_family_options,
+    rocksdb_column_family_handle_t** column_family_handles, const int* ttls,
+    char** errptr) {
+  std::vector<ColumnFamilyDescriptor> column_families;
+  std::vector<int32_t> ttl_values;
+  for (int i = 0; i < num_column_families; i++) {
+    column_families.push_back(ColumnFamilyDescriptor(
+        std::string(column_family_names[i]),
+        ColumnFamilyOptions(column_family_options[i]->rep)));
+    ttl_values.push_back(ttls[i]);
+  }
+
+  DBWithTTL* db;
+  std::vector<ColumnFamilyHandle*> handles;
+  if (SaveError(errptr, DBWithTTL::Open(DBOptions(db_options->rep),
+                                        std::string(name), column_families,
+                                        &handles, &db, ttl_values))) {
+    return nullptr;
+  }
+
+  for (size_t i = 0; i < handles.size(); i++) {
+    rocksdb_column_family_handle_t* c_handle =
+        new rocksdb_column_family_handle_t;
+    c_handle->rep = handles[i];
+    column_family_handles[i] = c_handle;
+  }
+  rocksdb_t* result = new rocksdb_t;
+  result->rep = db;
+  return result;
+}
+
+void rocksdb_ext_singledelete_cf(
+    rocksdb_t* db, const rocksdb_writeoptions_t* options,
+    rocksdb_column_family_handle_t* column_family, const char* key,
+    size_t key_len, char** errptr) {
+  ColumnFamilyHandle* cf = column_family == nullptr
+                               ? db->rep->DefaultColumnFamily()
+                               : column_family->rep;
+  SaveError(errptr,
+            db->rep->SingleDelete(options->rep, cf, Slice(key, key_len)));
+}
+
+unsigned char rocksdb_ext_key_may_exist_cf(
+    rocksdb_t* db, const rocksdb_readoptions_t* options,
+    rocksdb_column_family_handle_t* column_family, const char* key,
+    size_t key_len, char** value, size_t* value_len,
+    unsigned char* value_found) {
+  ColumnFamilyHandle* cf = column_family == nullptr
+                               ? db->rep->DefaultColumnFamily()
+                               : column_family->rep;
+  std::string tmp;
+  bool found = false;
+  const bool may_exist = db->rep->KeyMayExist(
+      options->rep, cf, Slice(key, key_len), &tmp, &found);
+  *value_found = found;
+  if (may_exist && found) {
+    *value_len = tmp.size();
+    *value = static_cast<char*>(malloc(tmp.size()));
+    memcpy(*value, tmp.data(), tmp.size());
+  } else {
+    *value = nullptr;
+    *value_len = 0;
+  }
+  return may_exist;
+}
+
+static std::unordered_map<std::string, std::string> OptionsMap(
+    int count, const char* const keys[], const char* const values[]) {
+  std::unordered_map<std::string, std::string> options;
+  for (int i = 0; i < count; i++) {
+    options[keys[i]] = values[i];
+  }
+  return options;
+}
+
+void rocksdb_ext_set_options_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family, int count,
+    const char* const keys[], const char* const values[], char** errptr) {
+  ColumnFamilyHandle* cf = column_family == nullptr
+                               ? db->rep->DefaultColumnFamily()
+                               : column_family->rep;
+  SaveError(errptr, db->rep->SetOptions(cf, OptionsMap(count, keys, values)));
+}
+
+void rocksdb_ext_set_db_options(rocksdb_t* db, int count,
+                                const char* const keys[],
+                                const char* const values[], char** errptr) {
+  SaveError(errptr, db->rep->SetDBOptions(OptionsMap(count, keys, values)));
+}
+
+size_t rocksdb_ext_iter_next_batch(
+    rocksdb_iterator_t* iter, size_t max_count, unsigned char reverse,
+    void* state,
+    void (*push)(void*, const char* k, size_t klen, const char* v,
+                 size_t vlen)) {
+  Iterator* it = iter->rep;
+  size_t count = 0;
+  while (count < max_count && it->Valid()) {
+    const Slice key = it->key();
+    const Slice value = it->value();
+    (*push)(state, key.data(), key.size(), value.data(), value.size());
+    count++;
+    if (reverse) {
+      it->Prev();
+    } else {
+      it->Next();
+    }
+  }
+  return count;
+}
+
+void rocksdb_ext_iter_refresh(rocksdb_iterator_t* iter, char** errptr) {
+  SaveError(errptr, iter->rep->Refresh());
+}
+
+uint64_t rocksdb_ext_snapshot_get_sequence_number(
+    const rocksdb_snapshot_t* snapshot) {
+  return snapshot->rep->GetSequenceNumber();
+}
+
+uint32_t rocksdb_ext_column_family_handle_get_id(
+    rocksdb_column_family_handle_t* handle) {
+  return handle->rep->GetID();
+}
+
+const char* rocksdb_ext_livefiles_column_family_name(
+    const rocksdb_livefiles_t* lf, int index) {
+  return lf->rep[index].column_family_name.c_str();
+}
+
+void rocksdb_ext_writebatch_singledelete_cf(
+    rocksdb_writebatch_t* b, rocksdb_column_family_handle_t* column_family,
+    const char* key, size_t key_len) {
+  if (column_family == nullptr) {
+    b->rep.SingleDelete(Slice(key, key_len));
+  } else {
+    b->rep.SingleDelete(column_family->rep, Slice(key, key_len));
+  }
+}
+
+void rocksdb_ext_writebatch_iterate_cf(
+    rocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cfid, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cfid, const char* k,
+                              size_t klen),
+    void (*merge_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    void (*delete_range_cf)(void*, uint32_t cfid, const char* begin_key,
+                            size_t begin_klen, const char* end_key,
+                            size_t end_klen),
+    char** errptr) {
+  class H : public WriteBatch::Handler {
+   public:
+    void* state_;
+    void (*put_cf_)(void*, uint32_t, const char*, size_t, const char*, size_t);
+    void (*deleted_cf_)(void*, uint32_t, const char*, size_t);
+    void (*single_deleted_cf_)(void*, uint32_t, const char*, size_t);
+    void (*merge_cf_)(void*, uint32_t, const char*, size_t, const char*,
+                      size_t);
+    void (*delete_range_cf_)(void*, uint32_t, const char*, size_t,
+                             const char*, size_t);
+    Status PutCF(uint32_t column_family_id, const Slice& key,
+                 const Slice& value) override {
+      (*put_cf_)(state_, column_family_id, key.data(), key.size(),
+                 value.data(), value.size());
+      return Status::OK();
+    }
+    Status DeleteCF(uint32_t column_family_id, const Slice& key) override {
+      (*deleted_cf_)(state_, column_family_id, key.data(), key.size());
+      return Status::OK();
+    }
+    Status SingleDeleteCF(uint32_t column_family_id,
+                          const Slice& key) override {
+      (*single_deleted_cf_)(state_, column_family_id, key.data(), key.size());
+      return Status::OK();
+    }
+    Status MergeCF(uint32_t column_family_id, const Slice& key,
+                   const Slice& value) override {
+      (*merge_cf_)(state_, column_family_id, key.data(), key.size(),
+                   value.data(), value.size());
+      return Status::OK();
+    }
+    Status DeleteRangeCF(uint32_t column_family_id, const Slice& begin_key,
+                         const Slice& end_key) override {
+      (*delete_range_cf_)(state_, column_family_id, begin_key.data(),
+                          begin_key.size(), end_key.data(), end_key.size());
+      return Status::OK();
+    }
+    void LogData(const Slice& /*blob*/) override {}
+  };
+  H handler;
+  handler.state_ = state;
+  handler.put_cf_ = put_cf;
+  handler.deleted_cf_ = deleted_cf;
+  handler.single_deleted_cf_ = single_deleted_cf;
+  handler.merge_cf_ = merge_cf;
+  handler.delete_range_cf_ = delete_range_cf;
+  SaveError(errptr, b->rep.Iterate(&handler));
+}
+
+rocksdb_wal_readoptions_t* rocksdb_ext_wal_readoptions_create(void) {
+  return new rocksdb_wal_readoptions_t;
+}
+
+void rocksdb_ext_wal_readoptions_destroy(rocksdb_wal_readoptions_t* opt) {
+  delete opt;
+}
+
+void rocksdb_ext_wal_readoptions_set_verify_checksums(
+    rocksdb_wal_readoptions_t* opt, unsigned char v) {
+  opt->rep.verify_checksums_ = v;
+}
+
+}  // end extern "C"
@@ -0,0 +1,365 @@
+/*
+ * C bindings for RocksDB functionality that is not (yet) exposed through
+ * `rocksdb/c.h`. Every function here is prefixed with `rocksdb_ext_` so it
+ * can not clash with symbols that a future RocksDB release adds to its own
+ * C API.
+ */
+
+#ifndef LIBROCKSDB_SYS_ROCKSDB_EXT_H_
+#define LIBROCKSDB_SYS_ROCKSDB_EXT_H_
+
+#include "rocksdb/c.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Options */
+
+extern void rocksdb_ext_options_set_manual_wal_flush(rocksdb_options_t* opt,
+                                                     unsigned char v);
+
+extern void rocksdb_ext_options_set_wal_bytes_per_sync(rocksdb_options_t* opt,
+                                                       uint64_t v);
+
+extern void rocksdb_ext_options_set_memtable_whole_key_filtering(
+    rocksdb_options_t* opt, unsigned char v);
+
+extern void rocksdb_ext_options_set_row_cache(rocksdb_options_t* opt,
+                                              rocksdb_cache_t* cache);
+
+extern void rocksdb_ext_options_set_skip_checking_sst_file_sizes_on_db_open(
+    rocksdb_options_t* opt, unsigned char v);
+
+extern void rocksdb_ext_options_set_stats_persist_period_sec(
+    rocksdb_options_t* opt, unsigned int v);
+
+/* `paths` and `target_sizes` both have `num_paths` entries. */
+extern void rocksdb_ext_options_set_db_paths(rocksdb_options_t* opt,
+                                             const char* const* paths,
+                                             const uint64_t* target_sizes,
+                                             size_t num_paths);
+
+extern void rocksdb_ext_options_set_cf_paths(rocksdb_options_t* opt,
+                                             const char* const* paths,
+                                             const uint64_t* target_sizes,
+                                             size_t num_paths);
+
+/* Write buffer manager */
+
+typedef struct rocksdb_ext_write_buffer_manager_t
+    rocksdb_ext_write_buffer_manager_t;
+
+extern rocksdb_ext_write_buffer_manager_t*
+rocksdb_ext_write_buffer_manager_create(size_t buffer_size);
+
+/* Charges the memtable memory to `cache` as well. */
+extern rocksdb_ext_write_buffer_manager_t*
+rocksdb_ext_write_buffer_manager_create_with_cache(size_t buffer_size,
+                                                   rocksdb_cache_t* cache);
+
+extern void rocksdb_ext_write_buffer_manager_destroy(
+    rocksdb_ext_write_buffer_manager_t* wbm);
+
+extern size_t rocksdb_ext_write_buffer_manager_memory_usage(
+    rocksdb_ext_write_buffer_manager_t* wbm);
+
+extern size_t rocksdb_ext_write_buffer_manager_buffer_size(
+    rocksdb_ext_write_buffer_manager_t* wbm);
+
+extern void rocksdb_ext_options_set_write_buffer_manager(
+    rocksdb_options_t* opt, rocksdb_ext_write_buffer_manager_t* wbm);
+
+/* Compaction */
+
+extern void rocksdb_ext_fifo_compaction_options_set_allow_compaction(
+    rocksdb_fifo_compaction_options_t* fifo_opts, unsigned char v);
+
+/* Event listener */
+
+typedef struct rocksdb_ext_flush_job_info_t {
+  const char* cf_name;
+  size_t cf_name_len;
+  const char* file_path;
+  size_t file_path_len;
+  int job_id;
+  unsigned char triggered_writes_slowdown;
+  unsigned char triggered_writes_stop;
+  uint64_t smallest_seqno;
+  uint64_t largest_seqno;
+} rocksdb_ext_flush_job_info_t;
+
+typedef struct rocksdb_ext_compaction_job_info_t {
+  const char* cf_name;
+  size_t cf_name_len;
+  /* NULL if the compaction succeeded. */
+  const char* status;
+  int job_id;
+  int base_input_level;
+  int output_level;
+  uint64_t elapsed_micros;
+  uint64_t num_input_files;
+  uint64_t num_output_files;
+  uint64_t num_input_records;
+  uint64_t num_output_records;
+  uint64_t total_input_bytes;
+  uint64_t total_output_bytes;
+} rocksdb_ext_compaction_job_info_t;
+
+/* Conditions are 0 (normal), 1 (delayed) or 2 (stopped). */
+typedef struct rocksdb_ext_write_stall_info_t {
+  const char* cf_name;
+  size_t cf_name_len;
+  int cur_condition;
+  int prev_condition;
+} rocksdb_ext_write_stall_info_t;
+
+typedef struct rocksdb_ext_eventlistener_t rocksdb_ext_eventlistener_t;
+
+/* The callbacks are invoked from background threads. `destructor` is called
+ * with `state` once the listener is no longer used by any options or DB. */
+extern rocksdb_ext_eventlistener_t* rocksdb_ext_eventlistener_create(
+    void* state, void (*destructor)(void*),
+    void (*on_flush_completed)(void*, const rocksdb_ext_flush_job_info_t*),
+    void (*on_compaction_completed)(void*,
+                                    const rocksdb_ext_compaction_job_info_t*),
+    void (*on_stall_conditions_changed)(void*,
+                                        const rocksdb_ext_write_stall_info_t*),
+    void (*on_background_error)(void*, int reason, const char* error));
+
+/* Adds the listener to the options, which share its ownership. The handle
+ * still has to be destroyed. */
+extern void rocksdb_ext_options_add_eventlistener(
+    rocksdb_options_t* opt, rocksdb_ext_eventlistener_t* listener);
+
+extern void rocksdb_ext_eventlistener_destroy(
+    rocksdb_ext_eventlistener_t* listener);
+
+/* Loads the options of the latest OPTIONS file of the database at `path`.
+ * On success, `*db_options` and each of the `*num_column_families` entries
+ * of `*column_family_options` must be destroyed with rocksdb_options_destroy,
+ * and the arrays released with rocksdb_ext_load_latest_options_destroy. */
+extern void rocksdb_ext_load_latest_options(
+    const char* path, unsigned char ignore_unknown_options,
+    rocksdb_options_t** db_options, size_t* num_column_families,
+    char*** column_family_names, rocksdb_options_t*** column_family_options,
+    char** errptr);
+
+extern void rocksdb_ext_load_latest_options_destroy(
+    char** column_family_names, rocksdb_options_t** column_family_options,
+    size_t num_column_families);
+
+/* Logger */
+
+typedef struct rocksdb_ext_logger_t rocksdb_ext_logger_t;
+
+/* `log` is called with the formatted lines of at least `log_level`, from any
+ * thread. `destructor` is called with `state` once the logger is no longer
+ * used by any options or DB. */
+extern rocksdb_ext_logger_t* rocksdb_ext_logger_create(
+    void* state, void (*destructor)(void*), int log_level,
+    void (*log)(void*, int level, const char* msg, size_t msg_len));
+
+/* Makes the options log through `logger`, which they share the ownership
+ * of. The handle still has to be destroyed. */
+extern void rocksdb_ext_options_set_info_log(rocksdb_options_t* opt,
+                                             rocksdb_ext_logger_t* logger);
+
+extern void rocksdb_ext_logger_destroy(rocksdb_ext_logger_t* logger);
+
+/* Merge operator */
+
+/* Like rocksdb_mergeoperator_create followed by
+ * rocksdb_options_set_merge_operator, with two differences. The merge
+ * callbacks can set `borrowed_value` to one of their inputs and return NULL
+ * instead of allocating a new value, and `allow_single_operand` makes RocksDB
+ * call `partial_merge` with a single operand. `partial_merge` can be NULL. */
+extern void rocksdb_ext_options_set_merge_operator(
+    rocksdb_options_t* opt, void* state, void (*destructor)(void*),
+    const char* (*name)(void*),
+    char* (*full_merge)(void*, const char* key, size_t key_length,
+                        const char* existing_value,
+                        size_t existing_value_length,
+                        const char* const* operands_list,
+                        const size_t* operands_list_length, int num_operands,
+                        unsigned char* success, const char** borrowed_value,
+                        size_t* new_value_length),
+    char* (*partial_merge)(void*, const char* key, size_t key_length,
+                           const char* const* operands_list,
+                           const size_t* operands_list_length,
+                           int num_operands, unsigned char* success,
+                           const char** borrowed_value,
+                           size_t* new_value_length),
+    void (*delete_value)(void*, const char* value, size_t value_length),
+    unsigned char allow_single_operand);
+
+/* Statistics */
+
+typedef struct rocksdb_ext_histogram_data_t {
+  double median;
+  double percentile95;
+  double percentile99;
+  double average;
+  double standard_deviation;
+  double max;
+  uint64_t count;
+  uint64_t sum;
+  double min;
+} rocksdb_ext_histogram_data_t;
+
+/* Returns 0 if statistics are disabled or the ticker name is unknown. */
+extern uint64_t rocksdb_ext_options_statistics_get_ticker_count(
+    rocksdb_options_t* opt, const char* name, size_t name_len);
+
+/* Returns 0 and leaves `data` untouched if statistics are disabled or the
+ * histogram name is unknown. */
+extern unsigned char rocksdb_ext_options_statistics_get_histogram_data(
+    rocksdb_options_t* opt, const char* name, size_t name_len,
+    rocksdb_ext_histogram_data_t* data);
+
+/* IO stats context */
+
+typedef struct rocksdb_ext_iostats_t {
+  uint64_t bytes_written;
+  uint64_t bytes_read;
+  uint64_t open_nanos;
+  uint64_t allocate_nanos;
+  uint64_t write_nanos;
+  uint64_t read_nanos;
+  uint64_t range_sync_nanos;
+  uint64_t fsync_nanos;
+  uint64_t prepare_write_nanos;
+  uint64_t logger_nanos;
+} rocksdb_ext_iostats_t;
+
+/* Both operate on the IO stats context of the calling thread. */
+extern void rocksdb_ext_iostats_context_reset(void);
+
+extern void rocksdb_ext_iostats_context_get(rocksdb_ext_iostats_t* stats);
+
+/* DB */
+
+extern void rocksdb_ext_flush_cfs(
+    rocksdb_t* db, const rocksdb_flushoptions_t* options,
+    rocksdb_column_family_handle_t** column_families, int num_column_families,
+    char** errptr);
+
+extern void rocksdb_ext_flush_wal(rocksdb_t* db, unsigned char sync,
+                                  char** errptr);
+
+extern void rocksdb_ext_sync_wal(rocksdb_t* db, char** errptr);
+
+extern void rocksdb_ext_pause_background_work(rocksdb_t* db, char** errptr);
+
+extern void rocksdb_ext_continue_background_work(rocksdb_t* db,
+                                                 char** errptr);
+
+/* Uses the default column family if `column_family` is NULL. */
+extern void rocksdb_ext_approximate_memtable_stats_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family,
+    const char* start_key, size_t start_key_len, const char* limit_key,
+    size_t limit_key_len, uint64_t* count, uint64_t* size);
+
+/* Like rocksdb_open_column_families, but opens a DBWithTTL where each column
+ * family expires its entries after its own entry of `ttls` (in seconds). */
+extern rocksdb_t* rocksdb_ext_open_column_families_with_ttl(
+    const rocksdb_options_t* options, const char* name,
+    int num_column_families, const char** column_family_names,
+    const rocksdb_options_t** column_family_options,
+    rocksdb_column_family_handle_t** column_family_handles, const int* ttls,
+    char** errptr);
+
+/* Uses the default column family if `column_family` is NULL. */
+extern void rocksdb_ext_singledelete_cf(
+    rocksdb_t* db, const rocksdb_writeoptions_t* options,
+    rocksdb_column_family_handle_t* column_family, const char* key,
+    size_t key_len, char** errptr);
+
+/* Uses the default column family if `column_family` is NULL. Returns 0 if
+ * the key definitely does not exist. If the value was found without IO, it
+ * is stored in a malloc()ed `*value` and `*value_found` is set to 1. */
+extern unsigned char rocksdb_ext_key_may_exist_cf(
+    rocksdb_t* db, const rocksdb_readoptions_t* options,
+    rocksdb_column_family_handle_t* column_family, const char* key,
+    size_t key_len, char** value, size_t* value_len,
+    unsigned char* value_found);
+
+/* Like rocksdb_set_options, but for the given column family. Uses the default
+ * column family if `column_family` is NULL. */
+extern void rocksdb_ext_set_options_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family, int count,
+    const char* const keys[], const char* const values[], char** errptr);
+
+extern void rocksdb_ext_set_db_options(rocksdb_t* db, int count,
+                                       const char* const keys[],
+                                       const char* const values[],
+                                       char** errptr);
+
+/* Iterator */
+
+/* Calls `push` with up to `max_count` entries, starting at the current
+ * position of `iter` and moving forward (or backward if `reverse` is set)
+ * after each one. Returns the number of entries pushed. */
+extern size_t rocksdb_ext_iter_next_batch(
+    rocksdb_iterator_t* iter, size_t max_count, unsigned char reverse,
+    void* state,
+    void (*push)(void*, const char* k, size_t klen, const char* v,
+                 size_t vlen));
+
+/* Makes `iter` read from the latest state of the database, releasing the
+ * resources pinned by the old one. The iterator must be repositioned with a
+ * seek afterwards. */
+extern void rocksdb_ext_iter_refresh(rocksdb_iterator_t* iter, char** errptr);
+
+/* Snapshot */
+
+extern uint64_t rocksdb_ext_snapshot_get_sequence_number(
+    const rocksdb_snapshot_t* snapshot);
+
+/* Column family */
+
+extern uint32_t rocksdb_ext_column_family_handle_get_id(
+    rocksdb_column_family_handle_t* handle);
+
+/* Live files */
+
+extern const char* rocksdb_ext_livefiles_column_family_name(
+    const rocksdb_livefiles_t* lf, int index);
+
+/* Write batch */
+
+/* Uses the default column family if `column_family` is NULL. */
+extern void rocksdb_ext_writebatch_singledelete_cf(
+    rocksdb_writebatch_t* b, rocksdb_column_family_handle_t* column_family,
+    const char* key, size_t key_len);
+
+/* Stores an error in `errptr` if the batch is corrupted. */
+extern void rocksdb_ext_writebatch_iterate_cf(
+    rocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cfid, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cfid, const char* k,
+                              size_t klen),
+    void (*merge_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    void (*delete_range_cf)(void*, uint32_t cfid, const char* begin_key,
+                            size_t begin_klen, const char* end_key,
+                            size_t end_klen),
+    char** errptr);
+
+/* WAL read options */
+
+extern rocksdb_wal_readoptions_t* rocksdb_ext_wal_readoptions_create(void);
+
+extern void rocksdb_ext_wal_readoptions_destroy(
+    rocksdb_wal_readoptions_t* opt);
+
+extern void rocksdb_ext_wal_readoptions_set_verify_checksums(
+    rocksdb_wal_readoptions_t* opt, unsigned char v);
+
+#ifdef __cplusplus
+} /* end extern "C" */
+#endif
+
+#endif /* LIBROCKSDB_SYS_ROCKSDB_EXT_H_ */
@@ -0,0 +1,136 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use tokio::runtime::Handle;
+use tokio::task;
+
+use crate::{Error, WriteBatch, DB};
+
+/// A handle to a `DB` for use from async code. Every operation runs on the
+/// blocking thread pool of a tokio runtime, so it never blocks the executor.
+///
+/// Cloning an `AsyncDB` is cheap; all clones share the same database, which
+/// is closed when the last clone is dropped.
+///
+/// Dropping the future of an operation does not cancel it: an operation that
+/// has started always runs to completion, so a write either fully happens or
+/// never starts.
+///
+/// Requires the `async` feature.
+///
+/// ```
+/// use rocksdb::{AsyncDB, DB, Options};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let path = "_path_for_rocksdb_storage_async";
+/// {
+///     let db = AsyncDB::new(DB::open_default(path).unwrap());
+///     db.put(b"k1".to_vec(), b"v1".to_vec()).await.unwrap();
+///     assert_eq!(db.get(b"k1".to_vec()).await.unwrap().unwrap(), b"v1");
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncDB {
+    db: Arc<DB>,
+    handle: Option<Handle>,
+}
+
+impl AsyncDB {
+    /// Runs the operations on the blocking pool of the runtime they are
+    /// awaited in.
+    pub fn new(db: DB) -> AsyncDB {
+        AsyncDB {
+            db: Arc::new(db),
+            handle: None,
+        }
+    }
+
+    /// Runs the operations on the blocking pool of the runtime of `handle`,
+    /// e.g. one dedicated to database work.
+    pub fn with_handle(db: DB, handle: Handle) -> AsyncDB {
+        AsyncDB {
+            db: Arc::new(db),
+            handle: Some(handle),
+        }
+    }
+
+    /// Returns the wrapped database, e.g. to run blocking calls that have no
+    /// async counterpart.
+    pub fn db(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    /// Runs `f` with the database on the blocking pool.
+    pub async fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&DB) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let db = self.db.clone();
+        let f = move || f(&db);
+        let task = match &self.handle {
+            Some(handle) => handle.enter(|| task::spawn_blocking(f)),
+            None => task::spawn_blocking(f),
+        };
+        match task.await {
+            Ok(result) => result,
+            Err(e) => match e.try_into_panic() {
+                Ok(panic) => std::panic::resume_unwind(panic),
+                Err(e) => panic!("database task failed: {}", e),
+            },
+        }
+    }
+
+    pub async fn get<K>(&self, key: K) -> Result<Option<Vec<u8>>, Error>
+    where
+        K: AsRef<[u8]> + Send + 'static,
+    {
+        self.run(move |db| db.get(key)).await
+    }
+
+    pub async fn multi_get<K>(&self, keys: Vec<K>) -> Vec<Result<Option<Vec<u8>>, Error>>
+    where
+        K: AsRef<[u8]> + Send + 'static,
+    {
+        self.run(move |db| db.multi_get(keys)).await
+    }
+
+    pub async fn put<K, V>(&self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]> + Send + 'static,
+        V: AsRef<[u8]> + Send + 'static,
+    {
+        self.run(move |db| db.put(key, value)).await
+    }
+
+    pub async fn delete<K>(&self, key: K) -> Result<(), Error>
+    where
+        K: AsRef<[u8]> + Send + 'static,
+    {
+        self.run(move |db| db.delete(key)).await
+    }
+
+    pub async fn write(&self, batch: WriteBatch) -> Result<(), Error> {
+        self.run(move |db| db.write(batch)).await
+    }
+
+    pub async fn flush(&self) -> Result<(), Error> {
+        self.run(|db| db.flush()).await
+    }
+}
@@ -19,6 +19,22 @@ use libc::c_int;
 use std::ffi::CString;
 use std::path::Path;
 
+/// Represents information of a backup including timestamp of the backup
+/// and the size (please note that sum of all backups' sizes is bigger than the actual
+/// size of the backup directory because some data is shared by multiple backups).
+/// Backups are identified by their always-increasing IDs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupEngineInfo {
+    /// Timestamp of the backup
+    pub timestamp: i64,
+    /// ID of the backup
+    pub backup_id: u32,
+    /// Size of the backup
+    pub size: u64,
+    /// Number of files related to the backup
+    pub num_files: u32,
+}
+
 pub struct BackupEngine {
     inner: *mut ffi::rocksdb_backup_engine_t,
 }
@@ -77,6 +93,41 @@ impl BackupEngine {
         }
     }
 
+    /// Checks that each file exists and that the size of the file matches our
+    /// expectations. It does not check file checksum.
+    pub fn verify_backup(&self, backup_id: u32) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_backup_engine_verify_backup(
+                self.inner, backup_id,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the metadata of every backup known to this backup engine.
+    pub fn get_backup_info(&self) -> Vec<BackupEngineInfo> {
+        unsafe {
+            let i = ffi::rocksdb_backup_engine_get_backup_info(self.inner);
+
+            let n = ffi::rocksdb_backup_engine_info_count(i);
+
+            let mut info = Vec::with_capacity(n as usize);
+            for index in 0..n {
+                info.push(BackupEngineInfo {
+                    timestamp: ffi::rocksdb_backup_engine_info_timestamp(i, index),
+                    backup_id: ffi::rocksdb_backup_engine_info_backup_id(i, index),
+                    size: ffi::rocksdb_backup_engine_info_size(i, index),
+                    num_files: ffi::rocksdb_backup_engine_info_number_files(i, index),
+                })
+            }
+
+            // destroy backup info object
+            ffi::rocksdb_backup_engine_info_destroy(i);
+
+            info
+        }
+    }
+
     /// Restore from the latest backup
     ///
     /// # Arguments
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{ffi, Options};
+use crate::{ffi, Options, DB};
+
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// The name of the default column family.
 ///
@@ -26,6 +30,7 @@ pub const DEFAULT_COLUMN_FAMILY_NAME: &str = "default";
 pub struct ColumnFamilyDescriptor {
     pub(crate) name: String,
     pub(crate) options: Options,
+    pub(crate) ttl: Option<Duration>,
 }
 
 impl ColumnFamilyDescriptor {
@@ -37,8 +42,62 @@ impl ColumnFamilyDescriptor {
         ColumnFamilyDescriptor {
             name: name.into(),
             options,
+            ttl: None,
+        }
+    }
+
+    /// Create a new column family descriptor whose entries expire after `ttl`
+    /// when opened with `DB::open_cf_descriptors_with_ttl`. Other ways of
+    /// opening a database ignore the TTL. TTLs longer than `c_int::MAX`
+    /// seconds are clamped to it.
+    pub fn new_with_ttl<S>(name: S, options: Options, ttl: Duration) -> Self
+    where
+        S: Into<String>,
+    {
+        ColumnFamilyDescriptor {
+            name: name.into(),
+            options,
+            ttl: Some(ttl),
         }
     }
+
+    /// Create a new column family descriptor for a column family that is only read
+    /// with point lookups, see `Options::optimize_for_point_lookup`.
+    pub fn new_for_point_lookup<S>(name: S, cache_size: u64) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut options = Options::default();
+        options.optimize_for_point_lookup(cache_size);
+        ColumnFamilyDescriptor::new(name, options)
+    }
+
+    /// Create a new column family descriptor using level style compaction tuned for
+    /// heavy workloads, see `Options::optimize_level_style_compaction`.
+    pub fn new_for_level_style_compaction<S>(name: S, memtable_memory_budget: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut options = Options::default();
+        options.optimize_level_style_compaction(memtable_memory_budget);
+        ColumnFamilyDescriptor::new(name, options)
+    }
+
+    /// Create a new column family descriptor using universal style compaction tuned
+    /// for heavy workloads, see `Options::optimize_universal_style_compaction`.
+    pub fn new_for_universal_style_compaction<S>(name: S, memtable_memory_budget: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut options = Options::default();
+        options.optimize_universal_style_compaction(memtable_memory_budget);
+        ColumnFamilyDescriptor::new(name, options)
+    }
+
+    /// Returns the name of the column family.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 /// An opaque type used to represent a column family. Returned from some functions, and used
@@ -47,4 +106,64 @@ pub struct ColumnFamily {
     pub(crate) inner: *mut ffi::rocksdb_column_family_handle_t,
 }
 
+impl ColumnFamily {
+    /// Returns the id RocksDB assigned to this column family. The default
+    /// column family always has id `0`.
+    pub fn id(&self) -> u32 {
+        unsafe { ffi::rocksdb_ext_column_family_handle_get_id(self.inner) }
+    }
+}
+
+impl Drop for ColumnFamily {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_column_family_handle_destroy(self.inner);
+        }
+    }
+}
+
 unsafe impl Send for ColumnFamily {}
+
+/// A column family handle which owns a reference to its database.
+///
+/// This is the handle to use for column families created and dropped while the database is
+/// shared in an `Arc`, as only its handles are freed without `DB::release_dropped_cfs`.
+///
+/// It dereferences to `ColumnFamily`, so it can be passed to all the `_cf` methods, and unlike
+/// the reference returned by `DB::cf_handle` it can be stored alongside an `Arc<DB>` or moved
+/// to another thread. The handle stays valid after the column family is dropped through
+/// `DB::drop_cf`, writes through it then fail instead of touching freed memory. The column
+/// family is freed once it is dropped and the last handle to it is gone.
+#[derive(Clone)]
+pub struct BoundColumnFamily {
+    // Declared before `db`, so the column family handle is destroyed before the database can be
+    // closed.
+    cf: Arc<ColumnFamily>,
+    db: Arc<DB>,
+}
+
+impl BoundColumnFamily {
+    /// Returns the handle of the column family `name` of `db`, or `None` if it doesn't exist.
+    pub fn new(db: Arc<DB>, name: &str) -> Option<BoundColumnFamily> {
+        let cf = db.cf_handle_shared(name)?;
+        Some(BoundColumnFamily { cf, db })
+    }
+
+    /// Returns the database the column family belongs to.
+    pub fn db(&self) -> &Arc<DB> {
+        &self.db
+    }
+}
+
+impl Deref for BoundColumnFamily {
+    type Target = ColumnFamily;
+
+    fn deref(&self) -> &ColumnFamily {
+        &self.cf
+    }
+}
+
+/// `Send` and `Sync` implementations for `BoundColumnFamily` are safe, because the handle is
+/// immutable and the database it belongs to is kept open.
+unsafe impl Send for BoundColumnFamily {}
+unsafe impl Sync for BoundColumnFamily {}
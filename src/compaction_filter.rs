@@ -13,10 +13,13 @@
 // limitations under the License.
 //
 
+use crate::ffi;
 use libc::{c_char, c_int, c_uchar, c_void, size_t};
+use std::convert::TryInto;
 use std::ffi::CString;
 use std::mem;
 use std::slice;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Decision about how to handle compacting an object
 ///
@@ -98,6 +101,172 @@ where
     }
 }
 
+/// Size of the write time appended to the values checked by `TtlCompactionFilter`.
+const TTL_TIMESTAMP_SIZE: usize = 4;
+
+/// Expires entries older than a time to live during compactions.
+///
+/// Unlike `DB::open_with_ttl`, it works with any database and can be set per column family
+/// through the options of its `ColumnFamilyDescriptor`. Values must be written with
+/// `encode_value`, which appends the write time in the format of `DB::open_with_ttl`, and read
+/// back with `decode_value`. Like with `DB::open_with_ttl`, expired entries are only removed
+/// when they get compacted, so they may still be read for a while.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::compaction_filter::TtlCompactionFilter;
+/// use rocksdb::{Options, DB};
+/// use std::time::Duration;
+///
+/// let path = "_path_for_rocksdb_storage_ttl_compaction_filter";
+/// {
+///     let mut opts = Options::default();
+///     opts.create_if_missing(true);
+///     opts.set_ttl_compaction_filter(TtlCompactionFilter::new(Duration::from_secs(3600)));
+///     let db = DB::open(&opts, path).unwrap();
+///     db.put(b"k1", TtlCompactionFilter::encode_value(b"v1")).unwrap();
+///     let value = db.get(b"k1").unwrap().unwrap();
+///     assert_eq!(TtlCompactionFilter::decode_value(&value), Some(&b"v1"[..]));
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TtlCompactionFilter {
+    ttl: Duration,
+}
+
+impl TtlCompactionFilter {
+    /// Creates a filter removing the entries written more than `ttl` ago.
+    pub fn new(ttl: Duration) -> TtlCompactionFilter {
+        TtlCompactionFilter { ttl }
+    }
+
+    /// Appends the current time to `value`.
+    pub fn encode_value(value: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(value.len() + TTL_TIMESTAMP_SIZE);
+        encoded.extend_from_slice(value);
+        encoded.extend_from_slice(&(now_secs() as u32).to_le_bytes());
+        encoded
+    }
+
+    /// Strips the write time from a value created by `encode_value`. Returns `None` if the
+    /// value is too short to contain one.
+    pub fn decode_value(value: &[u8]) -> Option<&[u8]> {
+        if value.len() < TTL_TIMESTAMP_SIZE {
+            return None;
+        }
+        Some(&value[..value.len() - TTL_TIMESTAMP_SIZE])
+    }
+
+    /// Decides whether an entry has expired. Values without a write time are kept.
+    pub fn filter(&self, _level: u32, _key: &[u8], value: &[u8]) -> Decision {
+        if value.len() < TTL_TIMESTAMP_SIZE {
+            return Decision::Keep;
+        }
+        let timestamp = &value[value.len() - TTL_TIMESTAMP_SIZE..];
+        let written = u32::from_le_bytes(timestamp.try_into().unwrap());
+        if u64::from(written) + self.ttl.as_secs() < now_secs() {
+            Decision::Remove
+        } else {
+            Decision::Keep
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Information about the compaction a filter is created for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactionFilterContext {
+    /// Whether this compaction includes all data files.
+    pub is_full_compaction: bool,
+    /// Whether this compaction was requested by the client (e.g. through
+    /// `compact_range`) rather than triggered automatically.
+    pub is_manual_compaction: bool,
+}
+
+impl CompactionFilterContext {
+    unsafe fn from_raw(ptr: *mut ffi::rocksdb_compactionfiltercontext_t) -> Self {
+        CompactionFilterContext {
+            is_full_compaction: ffi::rocksdb_compactionfiltercontext_is_full_compaction(ptr) != 0,
+            is_manual_compaction: ffi::rocksdb_compactionfiltercontext_is_manual_compaction(ptr)
+                != 0,
+        }
+    }
+}
+
+/// Creates a new compaction filter for every compaction.
+///
+/// Unlike a single compaction filter, a factory gets to see the context of
+/// each compaction, and every filter it creates is only used by one compaction
+/// at a time.
+///
+/// `create` may be called from several compaction threads at once, and the
+/// factory is shared by every database opened with the same `Options`, so it
+/// only gets shared access. Factories that need state can use interior
+/// mutability, e.g. an atomic counter or a `Mutex`.
+///
+///  See [Options::set_compaction_filter_factory][set_compaction_filter_factory] for more details
+///
+///  [set_compaction_filter_factory]: ../struct.Options.html#method.set_compaction_filter_factory
+pub trait CompactionFilterFactory {
+    type Filter: CompactionFilterFn + Send + 'static;
+
+    /// Returns the filter to use for a compaction with the given `context`.
+    fn create(&self, context: CompactionFilterContext) -> Self::Filter;
+}
+
+pub struct CompactionFilterFactoryCallback<F>
+where
+    F: CompactionFilterFactory,
+{
+    pub name: CString,
+    pub factory: F,
+}
+
+pub unsafe extern "C" fn factory_destructor_callback<F>(raw_cb: *mut c_void)
+where
+    F: CompactionFilterFactory,
+{
+    let _: Box<CompactionFilterFactoryCallback<F>> = mem::transmute(raw_cb);
+}
+
+pub unsafe extern "C" fn factory_name_callback<F>(raw_cb: *mut c_void) -> *const c_char
+where
+    F: CompactionFilterFactory,
+{
+    let cb = &*(raw_cb as *mut CompactionFilterFactoryCallback<F>);
+    cb.name.as_ptr()
+}
+
+pub unsafe extern "C" fn create_filter_callback<F>(
+    raw_cb: *mut c_void,
+    context: *mut ffi::rocksdb_compactionfiltercontext_t,
+) -> *mut ffi::rocksdb_compactionfilter_t
+where
+    F: CompactionFilterFactory,
+{
+    let cb = &*(raw_cb as *const CompactionFilterFactoryCallback<F>);
+    let context = CompactionFilterContext::from_raw(context);
+    let filter = Box::new(CompactionFilterCallback {
+        name: cb.name.clone(),
+        filter_fn: cb.factory.create(context),
+    });
+
+    // The filter is owned and destroyed by RocksDB once the compaction is done.
+    ffi::rocksdb_compactionfilter_create(
+        Box::into_raw(filter) as *mut c_void,
+        Some(destructor_callback::<F::Filter>),
+        Some(filter_callback::<F::Filter>),
+        Some(name_callback::<F::Filter>),
+    )
+}
+
 #[cfg(test)]
 #[allow(unused_variables)]
 fn test_filter(level: u32, key: &[u8], value: &[u8]) -> Decision {
@@ -130,3 +299,41 @@ fn compaction_filter_test() {
     let result = DB::destroy(&opts, path);
     assert!(result.is_ok());
 }
+
+#[cfg(test)]
+struct TestFilterFactory;
+
+#[cfg(test)]
+impl CompactionFilterFactory for TestFilterFactory {
+    type Filter = fn(u32, &[u8], &[u8]) -> Decision;
+
+    fn create(&self, context: CompactionFilterContext) -> Self::Filter {
+        if context.is_manual_compaction {
+            test_filter
+        } else {
+            |_, _, _| Decision::Keep
+        }
+    }
+}
+
+#[test]
+fn compaction_filter_factory_test() {
+    use crate::{Options, DB};
+
+    let path = "_rust_rocksdb_filterfactorytest";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compaction_filter_factory("test", TestFilterFactory);
+    {
+        let db = DB::open(&opts, path).unwrap();
+        let _ = db.put(b"k1", b"a");
+        let _ = db.put(b"_k", b"b");
+        let _ = db.put(b"%k", b"c");
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"a");
+        assert!(db.get(b"_k").unwrap().is_none());
+        assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
+    }
+    let result = DB::destroy(&opts, path);
+    assert!(result.is_ok());
+}
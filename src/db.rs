@@ -15,10 +15,11 @@
 
 use crate::{
     ffi,
-    ffi_util::{opt_bytes_to_ptr, to_cpath},
-    ColumnFamily, ColumnFamilyDescriptor, DBIterator, DBPinnableSlice, DBRawIterator,
-    DBWALIterator, Direction, Error, FlushOptions, IngestExternalFileOptions, IteratorMode,
-    Options, ReadOptions, Snapshot, WriteBatch, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
+    ffi_util::{error_message, opt_bytes_to_ptr, raw_data, to_cpath},
+    ColumnFamily, ColumnFamilyDescriptor, CompactOptions, DBIterator, DBKeysIterator,
+    DBPinnableSlice, DBRawIterator, DBWALIterator, Direction, Env, Error, FlushOptions,
+    IngestExternalFileOptions, IteratorMode, Options, ReadOptions, Snapshot, WalReadOptions,
+    WriteBatch, WriteBatchWithIndex, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
 };
 
 use libc::{self, c_char, c_int, c_uchar, c_void, size_t};
@@ -31,15 +32,28 @@ use std::path::PathBuf;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 use std::time::Duration;
 
 /// A RocksDB database.
 ///
+/// All operations, including creating and dropping column families, take `&self`, so a
+/// database can be shared between threads by wrapping it in an `Arc`.
+///
+/// A database shared this way that keeps creating and dropping column families should access
+/// them through `BoundColumnFamily` rather than `cf_handle`: the handles of a dropped column
+/// family returned by `cf_handle` can only be freed through `release_dropped_cfs`, which needs
+/// the database back out of its `Arc`.
+///
 /// See crate level documentation for a simple usage example.
 pub struct DB {
     pub(crate) inner: *mut ffi::rocksdb_t,
-    cfs: BTreeMap<String, ColumnFamily>,
+    cfs: ColumnFamilies,
     path: PathBuf,
+    // Keeps the `Env` set through `Options::set_env` alive while the database is open.
+    _env: Option<Env>,
+    cancel_background_work_on_drop: Option<bool>,
 }
 
 // Safety note: auto-implementing Send on most db-related types is prevented by the inner FFI
@@ -51,6 +65,161 @@ unsafe impl Send for DB {}
 // use within the rocksdb library is generally behind a const reference
 unsafe impl Sync for DB {}
 
+/// The column family handles of an open database.
+///
+/// Handles are reference counted and destroyed, which lets RocksDB free the memtables and files
+/// of a dropped column family, once they are removed by `drop_cf` and the last
+/// `BoundColumnFamily` using them is gone. The exception are handles lent out by
+/// `DB::cf_handle`: nothing tells when those references are no longer used, so once dropped
+/// they are kept until the database is closed or `DB::release_dropped_cfs` is called.
+#[derive(Default)]
+struct ColumnFamilies {
+    live: RwLock<BTreeMap<String, CfEntry>>,
+    dropped: Mutex<Vec<Arc<ColumnFamily>>>,
+}
+
+struct CfEntry {
+    cf: Arc<ColumnFamily>,
+    // Whether a reference to the handle was returned by `ColumnFamilies::get`.
+    lent: AtomicBool,
+}
+
+impl CfEntry {
+    fn new(cf: ColumnFamily) -> CfEntry {
+        CfEntry {
+            cf: Arc::new(cf),
+            lent: AtomicBool::new(false),
+        }
+    }
+}
+
+impl ColumnFamilies {
+    fn new(cfs: BTreeMap<String, ColumnFamily>) -> ColumnFamilies {
+        ColumnFamilies {
+            live: RwLock::new(
+                cfs.into_iter()
+                    .map(|(name, cf)| (name, CfEntry::new(cf)))
+                    .collect(),
+            ),
+            dropped: Mutex::default(),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&ColumnFamily> {
+        let live = self.live.read().unwrap_or_else(PoisonError::into_inner);
+        live.get(name).map(|entry| {
+            entry.lent.store(true, Ordering::Relaxed);
+            // Safety: lent handles are only destroyed by `destroy` and `release_dropped`, which
+            // take `&mut self`.
+            unsafe { &*(entry.cf.as_ref() as *const ColumnFamily) }
+        })
+    }
+
+    fn get_shared(&self, name: &str) -> Option<Arc<ColumnFamily>> {
+        let live = self.live.read().unwrap_or_else(PoisonError::into_inner);
+        live.get(name).map(|entry| entry.cf.clone())
+    }
+
+    fn name(&self, id: u32) -> Option<String> {
+        let live = self.live.read().unwrap_or_else(PoisonError::into_inner);
+        live.iter()
+            .find(|(_, entry)| entry.cf.id() == id)
+            .map(|(name, _)| name.clone())
+    }
+
+    fn insert(&self, name: String, cf: ColumnFamily) {
+        let mut live = self.live.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(old) = live.insert(name, CfEntry::new(cf)) {
+            self.retire(old);
+        }
+    }
+
+    /// Calls `f` with the handle of the column family `name` and, when it succeeds, removes the
+    /// handle from the live column families. `f` runs without holding the lock, so lookups on
+    /// other threads don't wait for it.
+    fn remove<F>(&self, name: &str, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&ColumnFamily) -> Result<(), Error>,
+    {
+        let cf = self
+            .get_shared(name)
+            .ok_or_else(|| Error::new(format!("Invalid column family: {}", name)))?;
+        f(&cf)?;
+        let mut live = self.live.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have dropped and recreated the column family in the meantime.
+        if live
+            .get(name)
+            .map_or(false, |entry| Arc::ptr_eq(&entry.cf, &cf))
+        {
+            if let Some(entry) = live.remove(name) {
+                self.retire(entry);
+            }
+        }
+        Ok(())
+    }
+
+    fn retire(&self, entry: CfEntry) {
+        if entry.lent.load(Ordering::Relaxed) {
+            self.dropped
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(entry.cf);
+        }
+    }
+
+    fn release_dropped(&mut self) {
+        self.dropped
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Releases the handles owned by the registry. The handles are destroyed before the database
+    /// is closed, as any other reference to them is held by a `BoundColumnFamily` keeping the
+    /// database open.
+    fn destroy(&mut self) {
+        self.live
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.release_dropped();
+    }
+}
+
+/// A key range `[start_key, end_key)`, used to estimate the size of data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range<'a> {
+    start_key: &'a [u8],
+    end_key: &'a [u8],
+}
+
+impl<'a> Range<'a> {
+    pub fn new(start_key: &'a [u8], end_key: &'a [u8]) -> Range<'a> {
+        Range { start_key, end_key }
+    }
+}
+
+/// The metadata that describes an SST file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveFile {
+    /// Name of the column family the file belongs to.
+    pub column_family: String,
+    /// Name of the file.
+    pub name: String,
+    /// Level at which this file resides.
+    pub level: i32,
+    /// File size in bytes.
+    pub size: usize,
+    /// Smallest user defined key in the file.
+    pub smallest_key: Option<Vec<u8>>,
+    /// Largest user defined key in the file.
+    pub largest_key: Option<Vec<u8>>,
+    /// Number of entries/alive keys in the file.
+    pub num_entries: u64,
+    /// Number of deletions/tomb key(s) in the file.
+    pub num_deletions: u64,
+}
+
 // Specifies whether open DB for read only.
 enum AccessType<'a> {
     ReadWrite,
@@ -91,6 +260,9 @@ impl DB {
     }
 
     /// Opens the database with a Time to Live compaction filter.
+    ///
+    /// RocksDB takes the TTL in seconds as a C `int`, so longer TTLs are clamped to
+    /// `c_int::MAX` seconds, about 68 years.
     pub fn open_with_ttl<P: AsRef<Path>>(
         opts: &Options,
         path: P,
@@ -104,8 +276,10 @@ impl DB {
 
         Ok(DB {
             inner: db,
-            cfs: BTreeMap::new(),
+            cfs: ColumnFamilies::default(),
             path: path.as_ref().to_path_buf(),
+            _env: opts.env.clone(),
+            cancel_background_work_on_drop: opts.cancel_background_work_on_drop,
         })
     }
 
@@ -186,6 +360,75 @@ impl DB {
         DB::open_cf_descriptors_internal(opts, path, cfs, &AccessType::ReadWrite)
     }
 
+    /// Opens a database for read only with the given database options and column family
+    /// descriptors.
+    pub fn open_cf_descriptors_read_only<P, I>(
+        opts: &Options,
+        path: P,
+        cfs: I,
+        error_if_log_file_exist: bool,
+    ) -> Result<DB, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = ColumnFamilyDescriptor>,
+    {
+        DB::open_cf_descriptors_internal(
+            opts,
+            path,
+            cfs,
+            &AccessType::ReadOnly {
+                error_if_log_file_exist,
+            },
+        )
+    }
+
+    /// Opens the database as a secondary with the given database options and column family
+    /// descriptors.
+    ///
+    /// The secondary follows the primary at `primary_path` and only sees its
+    /// newer writes after calling `try_catch_up_with_primary`.
+    pub fn open_cf_descriptors_as_secondary<P, S, I>(
+        opts: &Options,
+        primary_path: P,
+        secondary_path: S,
+        cfs: I,
+    ) -> Result<DB, Error>
+    where
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+        I: IntoIterator<Item = ColumnFamilyDescriptor>,
+    {
+        DB::open_cf_descriptors_internal(
+            opts,
+            primary_path,
+            cfs,
+            &AccessType::Secondary {
+                secondary_path: secondary_path.as_ref(),
+            },
+        )
+    }
+
+    /// Opens a database with a Time to Live compaction filter and the given column family
+    /// descriptors.
+    ///
+    /// Entries of each column family expire after the TTL of its descriptor (see
+    /// `ColumnFamilyDescriptor::new_with_ttl`), or after `ttl` if it has none. Expired
+    /// entries are removed during compaction, so they may still be read until then.
+    ///
+    /// TTLs are clamped like those of `open_with_ttl`.
+    pub fn open_cf_descriptors_with_ttl<P, I>(
+        opts: &Options,
+        path: P,
+        cfs: I,
+        ttl: Duration,
+    ) -> Result<DB, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = ColumnFamilyDescriptor>,
+    {
+        DB::open_cf_descriptors_internal(opts, path, cfs, &AccessType::WithTTL { ttl })
+    }
+
     /// Internal implementation for opening RocksDB.
     fn open_cf_descriptors_internal<P, I>(
         opts: &Options,
@@ -201,11 +444,15 @@ impl DB {
 
         let cpath = to_cpath(&path)?;
 
-        if let Err(e) = fs::create_dir_all(&path) {
-            return Err(Error::new(format!(
-                "Failed to create RocksDB directory: `{:?}`.",
-                e
-            )));
+        // A read-only database must already exist, don't leave an empty
+        // directory behind when it doesn't.
+        if !matches!(access_type, AccessType::ReadOnly { .. }) {
+            if let Err(e) = fs::create_dir_all(&path) {
+                return Err(Error::new(format!(
+                    "Failed to create RocksDB directory: `{:?}`.",
+                    e
+                )));
+            }
         }
 
         let db: *mut ffi::rocksdb_t;
@@ -217,10 +464,10 @@ impl DB {
             let mut cfs_v = cfs;
             // Always open the default column family.
             if !cfs_v.iter().any(|cf| cf.name == DEFAULT_COLUMN_FAMILY_NAME) {
-                cfs_v.push(ColumnFamilyDescriptor {
-                    name: String::from(DEFAULT_COLUMN_FAMILY_NAME),
-                    options: Options::default(),
-                });
+                cfs_v.push(ColumnFamilyDescriptor::new(
+                    DEFAULT_COLUMN_FAMILY_NAME,
+                    Options::default(),
+                ));
             }
             // We need to store our CStrings in an intermediate vector
             // so that their pointers remain valid.
@@ -267,8 +514,10 @@ impl DB {
 
         Ok(DB {
             inner: db,
-            cfs: cf_map,
+            cfs: ColumnFamilies::new(cf_map),
             path: path.as_ref().to_path_buf(),
+            _env: opts.env.clone(),
+            cancel_background_work_on_drop: opts.cancel_background_work_on_drop,
         })
     }
 
@@ -299,7 +548,7 @@ impl DB {
                 AccessType::WithTTL { ttl } => ffi_try!(ffi::rocksdb_open_with_ttl(
                     opts.inner,
                     cpath.as_ptr() as *const _,
-                    ttl.as_secs() as c_int,
+                    ttl_secs(ttl),
                 )),
             }
         };
@@ -347,7 +596,22 @@ impl DB {
                         cfhandles.as_mut_ptr(),
                     ))
                 }
-                _ => return Err(Error::new("Unsupported access type".to_owned())),
+                AccessType::WithTTL { ttl } => {
+                    let ttls: Vec<_> = cfs_v
+                        .iter()
+                        .map(|cf| ttl_secs(cf.ttl.unwrap_or(ttl)))
+                        .collect();
+
+                    ffi_try!(ffi::rocksdb_ext_open_column_families_with_ttl(
+                        opts.inner,
+                        cpath.as_ptr(),
+                        cfs_v.len() as c_int,
+                        cfnames.as_ptr(),
+                        cfopts.as_ptr(),
+                        cfhandles.as_mut_ptr(),
+                        ttls.as_ptr(),
+                    ))
+                }
             }
         };
         Ok(db)
@@ -381,6 +645,9 @@ impl DB {
         Ok(())
     }
 
+    /// Tries to recover as much data as possible of a corrupted database.
+    /// Some data may be lost, so be careful when calling this on a database
+    /// that contains important information.
     pub fn repair<P: AsRef<Path>>(opts: &Options, path: P) -> Result<(), Error> {
         let cpath = to_cpath(path)?;
         unsafe {
@@ -420,6 +687,108 @@ impl DB {
         self.flush_cf_opt(cf, &FlushOptions::default())
     }
 
+    /// Flushes database memtables to SST files on the disk for the given column families.
+    ///
+    /// The column families are flushed atomically if `Options::set_atomic_flush` is enabled.
+    pub fn flush_cfs_opt(
+        &self,
+        cfs: &[&ColumnFamily],
+        flushopts: &FlushOptions,
+    ) -> Result<(), Error> {
+        let mut cfs: Vec<_> = cfs.iter().map(|cf| cf.inner).collect();
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_flush_cfs(
+                self.inner,
+                flushopts.inner,
+                cfs.as_mut_ptr(),
+                cfs.len() as c_int,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Flushes database memtables to SST files on the disk for the given column families
+    /// using default options.
+    pub fn flush_cfs(&self, cfs: &[&ColumnFamily]) -> Result<(), Error> {
+        self.flush_cfs_opt(cfs, &FlushOptions::default())
+    }
+
+    /// Flushes the WAL buffer to the file. If `sync` is true, the file is also synced to disk.
+    ///
+    /// This is only useful with `Options::set_manual_wal_flush`; otherwise the WAL is
+    /// flushed after every write.
+    pub fn flush_wal(&self, sync: bool) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_flush_wal(self.inner, sync as c_uchar));
+        }
+        Ok(())
+    }
+
+    /// Syncs the WAL to disk. Writes done with `WriteOptions::set_sync(false)` are
+    /// durable once this returns.
+    pub fn sync_wal(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_sync_wal(self.inner));
+        }
+        Ok(())
+    }
+
+    /// Prevents the database from deleting obsolete files, e.g. while external
+    /// tooling copies the live files.
+    ///
+    /// Calls nest: file deletions are only enabled again once `enable_file_deletions`
+    /// was called as many times, unless it is forced.
+    pub fn disable_file_deletions(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_disable_file_deletions(self.inner));
+        }
+        Ok(())
+    }
+
+    /// Allows the database to delete obsolete files again. If `force` is true,
+    /// file deletions are enabled regardless of how many times
+    /// `disable_file_deletions` was called.
+    pub fn enable_file_deletions(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_enable_file_deletions(
+                self.inner,
+                force as c_uchar
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stops scheduling flushes and compactions and waits for the running ones to finish,
+    /// e.g. before taking a filesystem snapshot of the database directory.
+    ///
+    /// Calls nest: background work only resumes once `continue_background_work` was called
+    /// as many times.
+    pub fn pause_background_work(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_pause_background_work(self.inner));
+        }
+        Ok(())
+    }
+
+    /// Cancels all pending flushes and compactions. If `wait` is true, also waits for the
+    /// running ones to finish.
+    ///
+    /// The database cannot be written to afterwards, this is meant to be called right before
+    /// it is dropped.
+    pub fn cancel_all_background_work(&self, wait: bool) {
+        unsafe {
+            ffi::rocksdb_cancel_all_background_work(self.inner, wait as c_uchar);
+        }
+    }
+
+    /// Resumes the background work stopped by `pause_background_work`.
+    pub fn continue_background_work(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_continue_background_work(self.inner));
+        }
+        Ok(())
+    }
+
     pub fn write_opt(&self, batch: WriteBatch, writeopts: &WriteOptions) -> Result<(), Error> {
         unsafe {
             ffi_try!(ffi::rocksdb_write(self.inner, writeopts.inner, batch.inner));
@@ -431,6 +800,27 @@ impl DB {
         self.write_opt(batch, &WriteOptions::default())
     }
 
+    /// Writes the contents of a `WriteBatchWithIndex`. The batch is left
+    /// untouched and can be cleared or dropped afterwards.
+    pub fn write_wbwi_opt(
+        &self,
+        batch: &WriteBatchWithIndex,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_write_writebatch_wi(
+                self.inner,
+                writeopts.inner,
+                batch.inner
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn write_wbwi(&self, batch: &WriteBatchWithIndex) -> Result<(), Error> {
+        self.write_wbwi_opt(batch, &WriteOptions::default())
+    }
+
     pub fn write_without_wal(&self, batch: WriteBatch) -> Result<(), Error> {
         let mut wo = WriteOptions::new();
         wo.disable_wal(true);
@@ -480,6 +870,77 @@ impl DB {
         self.get_cf_opt(cf, key.as_ref(), &ReadOptions::default())
     }
 
+    /// Returns `false` if the key definitely does not exist in the database,
+    /// using only in-memory data like memtables, the block cache and bloom
+    /// filters. Otherwise returns `true`, along with the value if it was found
+    /// without reading from disk.
+    ///
+    /// This is cheaper than `get`, but may return `true` for a key that does
+    /// not exist.
+    pub fn key_may_exist_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> (bool, Option<Vec<u8>>) {
+        self.key_may_exist_raw(None, key.as_ref(), readopts)
+    }
+
+    /// See [`key_may_exist_opt`](#method.key_may_exist_opt).
+    pub fn key_may_exist<K: AsRef<[u8]>>(&self, key: K) -> (bool, Option<Vec<u8>>) {
+        self.key_may_exist_opt(key, &ReadOptions::default())
+    }
+
+    /// See [`key_may_exist_opt`](#method.key_may_exist_opt).
+    pub fn key_may_exist_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> (bool, Option<Vec<u8>>) {
+        self.key_may_exist_raw(Some(cf), key.as_ref(), readopts)
+    }
+
+    /// See [`key_may_exist_opt`](#method.key_may_exist_opt).
+    pub fn key_may_exist_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> (bool, Option<Vec<u8>>) {
+        self.key_may_exist_cf_opt(cf, key, &ReadOptions::default())
+    }
+
+    fn key_may_exist_raw(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> (bool, Option<Vec<u8>>) {
+        let mut val: *mut c_char = ptr::null_mut();
+        let mut val_len: size_t = 0;
+        let mut value_found: c_uchar = 0;
+        unsafe {
+            let may_exist = ffi::rocksdb_ext_key_may_exist_cf(
+                self.inner,
+                readopts.inner,
+                cf.map_or(ptr::null_mut(), |cf| cf.inner),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val,
+                &mut val_len,
+                &mut value_found,
+            ) != 0;
+            let value = if value_found != 0 {
+                Some(raw_data(val, val_len).unwrap_or_default())
+            } else {
+                None
+            };
+            if !val.is_null() {
+                libc::free(val as *mut c_void);
+            }
+            (may_exist, value)
+        }
+    }
+
     /// Return the value associated with a key using RocksDB's PinnableSlice
     /// so as to avoid unnecessary memory copy.
     pub fn get_pinned_opt<K: AsRef<[u8]>>(
@@ -563,7 +1024,104 @@ impl DB {
         self.get_pinned_cf_opt(cf, key, &ReadOptions::default())
     }
 
-    pub fn create_cf<N: AsRef<str>>(&mut self, name: N, opts: &Options) -> Result<(), Error> {
+    /// Return the values associated with the given keys.
+    pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_opt(keys, &ReadOptions::default())
+    }
+
+    /// Return the values associated with the given keys using read options.
+    pub fn multi_get_opt<K, I>(
+        &self,
+        keys: I,
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<Vec<u8>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        let keys_ptrs: Vec<*const c_char> = keys
+            .iter()
+            .map(|k| k.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let keys_sizes: Vec<size_t> = keys.iter().map(|k| k.as_ref().len() as size_t).collect();
+
+        let mut values = vec![ptr::null_mut(); keys.len()];
+        let mut values_sizes = vec![0 as size_t; keys.len()];
+        let mut errors = vec![ptr::null_mut(); keys.len()];
+        unsafe {
+            ffi::rocksdb_multi_get(
+                self.inner,
+                readopts.inner,
+                keys.len() as size_t,
+                keys_ptrs.as_ptr(),
+                keys_sizes.as_ptr(),
+                values.as_mut_ptr(),
+                values_sizes.as_mut_ptr(),
+                errors.as_mut_ptr(),
+            );
+        }
+
+        convert_values(values, values_sizes, errors)
+    }
+
+    /// Return the values associated with the given keys and column families.
+    pub fn multi_get_cf<'c, K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        self.multi_get_cf_opt(keys, &ReadOptions::default())
+    }
+
+    /// Return the values associated with the given keys and column families using read options.
+    pub fn multi_get_cf_opt<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<Vec<u8>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        let keys: Vec<(&ColumnFamily, K)> = keys.into_iter().collect();
+        let cfs: Vec<*const ffi::rocksdb_column_family_handle_t> =
+            keys.iter().map(|(cf, _)| cf.inner as *const _).collect();
+        let keys_ptrs: Vec<*const c_char> = keys
+            .iter()
+            .map(|(_, k)| k.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let keys_sizes: Vec<size_t> = keys
+            .iter()
+            .map(|(_, k)| k.as_ref().len() as size_t)
+            .collect();
+
+        let mut values = vec![ptr::null_mut(); keys.len()];
+        let mut values_sizes = vec![0 as size_t; keys.len()];
+        let mut errors = vec![ptr::null_mut(); keys.len()];
+        unsafe {
+            ffi::rocksdb_multi_get_cf(
+                self.inner,
+                readopts.inner,
+                cfs.as_ptr(),
+                keys.len() as size_t,
+                keys_ptrs.as_ptr(),
+                keys_sizes.as_ptr(),
+                values.as_mut_ptr(),
+                values_sizes.as_mut_ptr(),
+                errors.as_mut_ptr(),
+            );
+        }
+
+        convert_values(values, values_sizes, errors)
+    }
+
+    /// Creates a new column family with the given options.
+    pub fn create_cf<N: AsRef<str>>(&self, name: N, opts: &Options) -> Result<(), Error> {
         let cf_name = if let Ok(c) = CString::new(name.as_ref().as_bytes()) {
             c
         } else {
@@ -584,20 +1142,49 @@ impl DB {
         Ok(())
     }
 
-    pub fn drop_cf(&mut self, name: &str) -> Result<(), Error> {
-        if let Some(cf) = self.cfs.remove(name) {
-            unsafe {
-                ffi_try!(ffi::rocksdb_drop_column_family(self.inner, cf.inner));
-            }
+    /// Drops the column family with the given name.
+    ///
+    /// RocksDB only frees the memtables and files of the column family once its handles are
+    /// destroyed. Handles held by `BoundColumnFamily` are destroyed when the last of them is
+    /// dropped, so column families accessed only through it are freed while the database is
+    /// shared. Handles previously returned by `cf_handle` stay valid, and alive, until the
+    /// database is closed or `release_dropped_cfs` is called; writes through them fail once the
+    /// column family is dropped.
+    pub fn drop_cf(&self, name: &str) -> Result<(), Error> {
+        self.cfs.remove(name, |cf| unsafe {
+            ffi_try!(ffi::rocksdb_drop_column_family(self.inner, cf.inner));
             Ok(())
-        } else {
-            Err(Error::new(format!("Invalid column family: {}", name)))
-        }
+        })
+    }
+
+    /// Return the underlying column family handle.
+    ///
+    /// Column families that may be dropped while the database is shared in an `Arc` should be
+    /// accessed through `BoundColumnFamily::new` instead, see `drop_cf`.
+    pub fn cf_handle(&self, name: &str) -> Option<&ColumnFamily> {
+        self.cfs.get(name)
+    }
+
+    pub(crate) fn cf_handle_shared(&self, name: &str) -> Option<Arc<ColumnFamily>> {
+        self.cfs.get_shared(name)
+    }
+
+    /// Destroys the handles of dropped column families that were returned by `cf_handle`,
+    /// letting RocksDB free their memtables and files.
+    ///
+    /// This needs exclusive access to the database, so it is meant for databases that aren't
+    /// shared; shared ones should use `BoundColumnFamily`, whose handles need no release.
+    pub fn release_dropped_cfs(&mut self) {
+        self.cfs.release_dropped();
     }
 
-    /// Return the underlying column family handle.
-    pub fn cf_handle(&self, name: &str) -> Option<&ColumnFamily> {
-        self.cfs.get(name)
+    /// Return the name of the column family with the given id, e.g. one reported
+    /// by `WriteBatch::iterate_cf` while tailing the WAL.
+    pub fn cf_name(&self, id: u32) -> Option<String> {
+        if id == 0 {
+            return Some(DEFAULT_COLUMN_FAMILY_NAME.to_owned());
+        }
+        self.cfs.name(id)
     }
 
     pub fn iterator<'a: 'b, 'b>(&'a self, mode: IteratorMode) -> DBIterator<'b> {
@@ -613,6 +1200,11 @@ impl DB {
         DBIterator::new(self, readopts, mode)
     }
 
+    /// Creates an iterator over the keys only, see `DBIterator::keys`.
+    pub fn keys_iterator<'a: 'b, 'b>(&'a self, mode: IteratorMode) -> DBKeysIterator<'b> {
+        self.iterator(mode).keys()
+    }
+
     /// Opens an iterator using the provided ReadOptions.
     /// This is used when you want to iterate over a specific ColumnFamily with a modified ReadOptions
     pub fn iterator_cf_opt<'a: 'b, 'b>(
@@ -643,6 +1235,16 @@ impl DB {
         )
     }
 
+    /// Opens an iterator over the keys starting with `prefix` in reverse order, so that the
+    /// first item is the last key with that prefix.
+    ///
+    /// Unlike `prefix_iterator`, the keys are matched byte by byte and the prefix extractor
+    /// is not used.
+    pub fn prefix_iterator_rev<'a: 'b, 'b, P: AsRef<[u8]>>(&'a self, prefix: P) -> DBIterator<'b> {
+        let opts = prefix_bounds_readopts(prefix.as_ref());
+        DBIterator::new(self, opts, IteratorMode::End)
+    }
+
     pub fn iterator_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
@@ -677,6 +1279,16 @@ impl DB {
         )
     }
 
+    /// Like `prefix_iterator_rev`, but for the given column family.
+    pub fn prefix_iterator_rev_cf<'a: 'b, 'b, P: AsRef<[u8]>>(
+        &'a self,
+        cf_handle: &ColumnFamily,
+        prefix: P,
+    ) -> DBIterator<'b> {
+        let opts = prefix_bounds_readopts(prefix.as_ref());
+        DBIterator::new_cf(self, cf_handle, opts, IteratorMode::End)
+    }
+
     /// Opens a raw iterator over the database, using the default read options
     pub fn raw_iterator<'a: 'b, 'b>(&'a self) -> DBRawIterator<'b> {
         let opts = ReadOptions::default();
@@ -843,6 +1455,66 @@ impl DB {
         }
     }
 
+    /// Removes the database entry for `key`, which must have been written at
+    /// most once since its last removal (and not merged). Unlike `delete_opt`,
+    /// the tombstone disappears together with the value during compaction.
+    /// Mixing single deletes with puts overwriting a key, deletes or merges
+    /// results in undefined behavior.
+    pub fn single_delete_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_singledelete_cf(
+                self.inner,
+                writeopts.inner,
+                ptr::null_mut(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    /// Like [`single_delete_opt`](#method.single_delete_opt), for a column family.
+    pub fn single_delete_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_singledelete_cf(
+                self.inner,
+                writeopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    /// Removes the database entries of the default column family in the range
+    /// `["from", "to")` using given write options.
+    pub fn delete_range_opt<K: AsRef<[u8]>>(
+        &self,
+        from: K,
+        to: K,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        // The C API has no `DeleteRange` for the default column family, but a
+        // single-entry batch gives the same result.
+        let mut batch = WriteBatch::default();
+        batch.delete_range(from, to);
+        self.write_opt(batch, writeopts)
+    }
+
     /// Removes the database entries in the range `["from", "to")` using given write options.
     pub fn delete_range_cf_opt<K: AsRef<[u8]>>(
         &self,
@@ -908,6 +1580,22 @@ impl DB {
         self.delete_cf_opt(cf, key.as_ref(), &WriteOptions::default())
     }
 
+    /// See [`single_delete_opt`](#method.single_delete_opt).
+    pub fn single_delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), Error> {
+        self.single_delete_opt(key.as_ref(), &WriteOptions::default())
+    }
+
+    /// See [`single_delete_cf_opt`](#method.single_delete_cf_opt).
+    pub fn single_delete_cf<K: AsRef<[u8]>>(&self, cf: &ColumnFamily, key: K) -> Result<(), Error> {
+        self.single_delete_cf_opt(cf, key.as_ref(), &WriteOptions::default())
+    }
+
+    /// Removes the database entries of the default column family in the range
+    /// `["from", "to")` using default write options.
+    pub fn delete_range<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<(), Error> {
+        self.delete_range_opt(from, to, &WriteOptions::default())
+    }
+
     /// Removes the database entries in the range `["from", "to")` using default write options.
     pub fn delete_range_cf<K: AsRef<[u8]>>(
         &self,
@@ -954,22 +1642,56 @@ impl DB {
         }
     }
 
-    pub fn set_options(&self, opts: &[(&str, &str)]) -> Result<(), Error> {
-        let copts = opts
-            .iter()
-            .map(|(name, value)| {
-                let cname = match CString::new(name.as_bytes()) {
-                    Ok(cname) => cname,
-                    Err(e) => return Err(Error::new(format!("Invalid option name `{}`", e))),
-                };
-                let cvalue = match CString::new(value.as_bytes()) {
-                    Ok(cvalue) => cvalue,
-                    Err(e) => return Err(Error::new(format!("Invalid option value: `{}`", e))),
-                };
-                Ok((cname, cvalue))
-            })
-            .collect::<Result<Vec<(CString, CString)>, Error>>()?;
+    /// Same as `compact_range` but with custom options.
+    pub fn compact_range_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    ) {
+        unsafe {
+            let start = start.as_ref().map(AsRef::as_ref);
+            let end = end.as_ref().map(AsRef::as_ref);
+
+            ffi::rocksdb_compact_range_opt(
+                self.inner,
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
+
+    /// Same as `compact_range_cf` but with custom options.
+    pub fn compact_range_cf_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    ) {
+        unsafe {
+            let start = start.as_ref().map(AsRef::as_ref);
+            let end = end.as_ref().map(AsRef::as_ref);
+
+            ffi::rocksdb_compact_range_cf_opt(
+                self.inner,
+                cf.inner,
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
 
+    /// Changes the mutable options of the default column family, e.g.
+    /// `write_buffer_size` or `disable_auto_compactions`, without reopening the database.
+    pub fn set_options(&self, opts: &[(&str, &str)]) -> Result<(), Error> {
+        let copts = to_cstring_pairs(opts)?;
         let cnames: Vec<*const c_char> = copts.iter().map(|opt| opt.0.as_ptr()).collect();
         let cvalues: Vec<*const c_char> = copts.iter().map(|opt| opt.1.as_ptr()).collect();
         let count = opts.len() as i32;
@@ -984,6 +1706,41 @@ impl DB {
         Ok(())
     }
 
+    /// Changes the mutable options of the given column family.
+    pub fn set_options_cf(&self, cf: &ColumnFamily, opts: &[(&str, &str)]) -> Result<(), Error> {
+        let copts = to_cstring_pairs(opts)?;
+        let cnames: Vec<*const c_char> = copts.iter().map(|opt| opt.0.as_ptr()).collect();
+        let cvalues: Vec<*const c_char> = copts.iter().map(|opt| opt.1.as_ptr()).collect();
+        let count = opts.len() as i32;
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_set_options_cf(
+                self.inner,
+                cf.inner,
+                count,
+                cnames.as_ptr(),
+                cvalues.as_ptr(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Changes the mutable database wide options, e.g. `max_background_jobs`.
+    pub fn set_db_options(&self, opts: &[(&str, &str)]) -> Result<(), Error> {
+        let copts = to_cstring_pairs(opts)?;
+        let cnames: Vec<*const c_char> = copts.iter().map(|opt| opt.0.as_ptr()).collect();
+        let cvalues: Vec<*const c_char> = copts.iter().map(|opt| opt.1.as_ptr()).collect();
+        let count = opts.len() as i32;
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_set_db_options(
+                self.inner,
+                count,
+                cnames.as_ptr(),
+                cvalues.as_ptr(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Retrieves a RocksDB property by name.
     ///
     /// Full list of properties could be find
@@ -1100,6 +1857,188 @@ impl DB {
         }
     }
 
+    /// Returns the approximate file system space used by keys in each of the
+    /// given ranges of the default column family. Data in memtables is not
+    /// included.
+    pub fn approximate_sizes(&self, ranges: &[Range]) -> Vec<u64> {
+        self.approximate_sizes_cf_internal(None, ranges)
+    }
+
+    /// Returns the approximate file system space used by keys in each of the
+    /// given ranges of the column family. Data in memtables is not included.
+    pub fn approximate_sizes_cf(&self, cf: &ColumnFamily, ranges: &[Range]) -> Vec<u64> {
+        self.approximate_sizes_cf_internal(Some(cf), ranges)
+    }
+
+    fn approximate_sizes_cf_internal(
+        &self,
+        cf: Option<&ColumnFamily>,
+        ranges: &[Range],
+    ) -> Vec<u64> {
+        let start_keys: Vec<*const c_char> = ranges
+            .iter()
+            .map(|r| r.start_key.as_ptr() as *const c_char)
+            .collect();
+        let start_key_lens: Vec<size_t> = ranges.iter().map(|r| r.start_key.len()).collect();
+        let end_keys: Vec<*const c_char> = ranges
+            .iter()
+            .map(|r| r.end_key.as_ptr() as *const c_char)
+            .collect();
+        let end_key_lens: Vec<size_t> = ranges.iter().map(|r| r.end_key.len()).collect();
+        let mut sizes: Vec<u64> = vec![0; ranges.len()];
+
+        unsafe {
+            match cf {
+                None => ffi::rocksdb_approximate_sizes(
+                    self.inner,
+                    ranges.len() as c_int,
+                    start_keys.as_ptr(),
+                    start_key_lens.as_ptr(),
+                    end_keys.as_ptr(),
+                    end_key_lens.as_ptr(),
+                    sizes.as_mut_ptr(),
+                ),
+                Some(cf) => ffi::rocksdb_approximate_sizes_cf(
+                    self.inner,
+                    cf.inner,
+                    ranges.len() as c_int,
+                    start_keys.as_ptr(),
+                    start_key_lens.as_ptr(),
+                    end_keys.as_ptr(),
+                    end_key_lens.as_ptr(),
+                    sizes.as_mut_ptr(),
+                ),
+            }
+        }
+        sizes
+    }
+
+    /// Returns the approximate number of entries and their total size (in
+    /// bytes) in the memtables of the default column family within `range`.
+    pub fn approximate_memtable_stats(&self, range: Range) -> (u64, u64) {
+        self.approximate_memtable_stats_cf_internal(None, range)
+    }
+
+    /// Returns the approximate number of entries and their total size (in
+    /// bytes) in the memtables of the column family within `range`.
+    pub fn approximate_memtable_stats_cf(&self, cf: &ColumnFamily, range: Range) -> (u64, u64) {
+        self.approximate_memtable_stats_cf_internal(Some(cf), range)
+    }
+
+    fn approximate_memtable_stats_cf_internal(
+        &self,
+        cf: Option<&ColumnFamily>,
+        range: Range,
+    ) -> (u64, u64) {
+        let mut count: u64 = 0;
+        let mut size: u64 = 0;
+        unsafe {
+            ffi::rocksdb_ext_approximate_memtable_stats_cf(
+                self.inner,
+                cf.map_or(ptr::null_mut(), |cf| cf.inner),
+                range.start_key.as_ptr() as *const c_char,
+                range.start_key.len() as size_t,
+                range.end_key.as_ptr() as *const c_char,
+                range.end_key.len() as size_t,
+                &mut count,
+                &mut size,
+            );
+        }
+        (count, size)
+    }
+
+    /// Delete SST files whose keys are entirely in the given range.
+    ///
+    /// Could leave some keys in the range which are in files which are not
+    /// entirely in the range.
+    ///
+    /// Note: L0 files are left regardless of whether they're in the range.
+    ///
+    /// Snapshots before the delete might not see the data in the given range.
+    pub fn delete_file_in_range<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<(), Error> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        unsafe {
+            ffi_try!(ffi::rocksdb_delete_file_in_range(
+                self.inner,
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    /// Same as `delete_file_in_range` but only for specific column family
+    pub fn delete_file_in_range_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        from: K,
+        to: K,
+    ) -> Result<(), Error> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        unsafe {
+            ffi_try!(ffi::rocksdb_delete_file_in_range_cf(
+                self.inner,
+                cf.inner,
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    /// Returns a list of all table files with their level, key range and entry counts.
+    pub fn live_files(&self) -> Result<Vec<LiveFile>, Error> {
+        unsafe {
+            let files = ffi::rocksdb_livefiles(self.inner);
+            if files.is_null() {
+                return Err(Error::new("Could not get live files".to_owned()));
+            }
+
+            let n = ffi::rocksdb_livefiles_count(files);
+            let mut livefiles = Vec::with_capacity(n as usize);
+            let mut key_size: usize = 0;
+
+            for i in 0..n {
+                let column_family =
+                    CStr::from_ptr(ffi::rocksdb_ext_livefiles_column_family_name(files, i))
+                        .to_string_lossy()
+                        .into_owned();
+                let name = CStr::from_ptr(ffi::rocksdb_livefiles_name(files, i))
+                    .to_string_lossy()
+                    .into_owned();
+                let level = ffi::rocksdb_livefiles_level(files, i);
+                let size = ffi::rocksdb_livefiles_size(files, i);
+
+                let smallest_key = ffi::rocksdb_livefiles_smallestkey(files, i, &mut key_size);
+                let smallest_key = raw_data(smallest_key, key_size);
+
+                let largest_key = ffi::rocksdb_livefiles_largestkey(files, i, &mut key_size);
+                let largest_key = raw_data(largest_key, key_size);
+
+                livefiles.push(LiveFile {
+                    column_family,
+                    name,
+                    level,
+                    size,
+                    smallest_key,
+                    largest_key,
+                    num_entries: ffi::rocksdb_livefiles_entries(files, i),
+                    num_deletions: ffi::rocksdb_livefiles_deletions(files, i),
+                });
+            }
+
+            ffi::rocksdb_livefiles_destroy(files);
+
+            Ok(livefiles)
+        }
+    }
+
     /// The sequence number of the most recent transaction.
     pub fn latest_sequence_number(&self) -> u64 {
         unsafe { ffi::rocksdb_get_latest_sequence_number(self.inner) }
@@ -1116,12 +2055,19 @@ impl DB {
     /// Calling `get_updates_since()` with a sequence number that is out of
     /// bounds will return an error.
     pub fn get_updates_since(&self, seq_number: u64) -> Result<DBWALIterator, Error> {
+        self.get_updates_since_opt(seq_number, &WalReadOptions::default())
+    }
+
+    /// Like `get_updates_since`, but reads the WAL with the given options.
+    pub fn get_updates_since_opt(
+        &self,
+        seq_number: u64,
+        opts: &WalReadOptions,
+    ) -> Result<DBWALIterator, Error> {
         unsafe {
-            // rocksdb_wal_readoptions_t does not appear to have any functions
-            // for creating and destroying it; fortunately we can pass a nullptr
-            // here to get the default behavior
-            let opts: *const ffi::rocksdb_wal_readoptions_t = ptr::null();
-            let iter = ffi_try!(ffi::rocksdb_get_updates_since(self.inner, seq_number, opts));
+            let iter = ffi_try!(ffi::rocksdb_get_updates_since(
+                self.inner, seq_number, opts.inner
+            ));
             Ok(DBWALIterator { inner: iter })
         }
     }
@@ -1225,9 +2171,10 @@ impl DB {
 impl Drop for DB {
     fn drop(&mut self) {
         unsafe {
-            for cf in self.cfs.values() {
-                ffi::rocksdb_column_family_handle_destroy(cf.inner);
+            if let Some(wait) = self.cancel_background_work_on_drop {
+                ffi::rocksdb_cancel_all_background_work(self.inner, wait as c_uchar);
             }
+            self.cfs.destroy();
             ffi::rocksdb_close(self.inner);
         }
     }
@@ -1239,6 +2186,78 @@ impl fmt::Debug for DB {
     }
 }
 
+/// Returns read options bounding iteration to the keys starting with `prefix`.
+pub(crate) fn prefix_bounds_readopts(prefix: &[u8]) -> ReadOptions {
+    let mut opts = ReadOptions::default();
+    // The bounds don't have the same prefix, so a prefix seek could miss keys.
+    opts.set_total_order_seek(true);
+    opts.set_iterate_lower_bound(prefix);
+    if let Some(upper) = prefix_successor(prefix) {
+        opts.set_iterate_upper_bound(upper);
+    }
+    opts
+}
+
+/// Returns the smallest key greater than all the keys starting with `prefix`, if any.
+fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(last) = successor.pop() {
+        if last < u8::max_value() {
+            successor.push(last + 1);
+            return Some(successor);
+        }
+    }
+    None
+}
+
+/// Returns the seconds of a TTL as the C `int` RocksDB takes, clamped to `c_int::MAX` instead of
+/// wrapping around to a negative, i.e. infinite, TTL.
+fn ttl_secs(ttl: Duration) -> c_int {
+    ttl.as_secs().min(c_int::max_value() as u64) as c_int
+}
+
+/// Converts option names and values to C strings for the `set_options` family.
+fn to_cstring_pairs(opts: &[(&str, &str)]) -> Result<Vec<(CString, CString)>, Error> {
+    opts.iter()
+        .map(|(name, value)| {
+            let cname = match CString::new(name.as_bytes()) {
+                Ok(cname) => cname,
+                Err(e) => return Err(Error::new(format!("Invalid option name `{}`", e))),
+            };
+            let cvalue = match CString::new(value.as_bytes()) {
+                Ok(cvalue) => cvalue,
+                Err(e) => return Err(Error::new(format!("Invalid option value: `{}`", e))),
+            };
+            Ok((cname, cvalue))
+        })
+        .collect()
+}
+
+/// Converts the raw output arrays of `rocksdb_multi_get*` into per-key results,
+/// freeing the memory allocated by RocksDB.
+fn convert_values(
+    values: Vec<*mut c_char>,
+    values_sizes: Vec<size_t>,
+    errors: Vec<*mut c_char>,
+) -> Vec<Result<Option<Vec<u8>>, Error>> {
+    values
+        .into_iter()
+        .zip(values_sizes)
+        .zip(errors)
+        .map(|((value, size), err)| {
+            if err.is_null() {
+                unsafe {
+                    let data = raw_data(value, size as usize);
+                    libc::free(value as *mut c_void);
+                    Ok(data)
+                }
+            } else {
+                Err(Error::new(error_message(err)))
+            }
+        })
+        .collect()
+}
+
 #[test]
 fn test_open_for_read_only() {
     let path = "_rust_rocksdb_test_open_for_read_only";
@@ -1257,6 +2276,14 @@ fn test_open_for_read_only() {
     assert!(DB::destroy(&opts, path).is_ok());
 }
 
+#[test]
+fn test_open_for_read_only_missing_db() {
+    let path = "_rust_rocksdb_test_open_for_read_only_missing_db";
+    let opts = Options::default();
+    assert!(DB::open_for_read_only(&opts, path, false).is_err());
+    assert!(!Path::new(path).exists());
+}
+
 #[test]
 fn test_open_cf_for_read_only() {
     let path = "_rust_rocksdb_test_open_cf_for_read_only";
@@ -1439,6 +2466,33 @@ fn iterator_test_upper_bound() {
     DB::destroy(&opts, path).unwrap();
 }
 
+#[test]
+fn iterator_test_lower_bound() {
+    let path = "_rust_rocksdb_iteratortest_lower_bound";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+        db.put(b"k4", b"v4").unwrap();
+        db.put(b"k5", b"v5").unwrap();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_iterate_lower_bound(b"k2".to_vec());
+        readopts.set_iterate_upper_bound(b"k4".to_vec());
+        readopts.set_fill_cache(false);
+
+        let iter = db.iterator_opt(IteratorMode::End, readopts);
+        let expected: Vec<_> = vec![(b"k3", b"v3"), (b"k2", b"v2")]
+            .into_iter()
+            .map(|(k, v)| (k.to_vec().into_boxed_slice(), v.to_vec().into_boxed_slice()))
+            .collect();
+        assert_eq!(expected, iter.collect::<Vec<_>>());
+    }
+    let opts = Options::default();
+    DB::destroy(&opts, path).unwrap();
+}
+
 #[test]
 fn iterator_test_tailing() {
     let path = "_rust_rocksdb_iteratortest_tailing";
@@ -1495,6 +2549,24 @@ fn snapshot_test() {
     assert!(DB::destroy(&opts, path).is_ok());
 }
 
+#[test]
+fn prefix_successor_test() {
+    assert_eq!(prefix_successor(b"abc"), Some(b"abd".to_vec()));
+    assert_eq!(prefix_successor(b"ab\xff"), Some(b"ac".to_vec()));
+    assert_eq!(prefix_successor(b"\xff\xff"), None);
+    assert_eq!(prefix_successor(b""), None);
+}
+
+#[test]
+fn ttl_secs_test() {
+    assert_eq!(ttl_secs(Duration::from_secs(60)), 60);
+    assert_eq!(ttl_secs(Duration::from_secs(1 << 31)), c_int::max_value());
+    assert_eq!(
+        ttl_secs(Duration::from_secs(u64::max_value())),
+        c_int::max_value()
+    );
+}
+
 #[test]
 fn set_option_test() {
     let path = "_rust_rocksdb_set_optionstest";
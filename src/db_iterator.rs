@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{ffi, ColumnFamily, Error, ReadOptions, WriteBatch, DB};
-use libc::{c_char, c_uchar, size_t};
+use crate::{ffi, ColumnFamily, Error, ReadOptions, WriteBatch, WriteBatchWithIndex, DB};
+use libc::{c_char, c_uchar, c_void, size_t};
 use std::marker::PhantomData;
 use std::slice;
 
@@ -68,9 +68,9 @@ use std::slice;
 pub struct DBRawIterator<'a> {
     inner: *mut ffi::rocksdb_iterator_t,
 
-    /// When iterate_upper_bound is set, the inner C iterator keeps a pointer to the upper bound
-    /// inside `_readopts`. Storing this makes sure the upper bound is always alive when the
-    /// iterator is being used.
+    /// When iterate_upper_bound or iterate_lower_bound is set, the inner C iterator keeps a
+    /// pointer to the bound inside `_readopts`. Storing this makes sure the bounds are always
+    /// alive when the iterator is being used.
     _readopts: ReadOptions,
 
     db: PhantomData<&'a DB>,
@@ -101,6 +101,35 @@ impl<'a> DBRawIterator<'a> {
         }
     }
 
+    /// Creates an iterator over the writes of `batch` merged on top of the
+    /// database (or one of its column families).
+    pub(crate) fn new_with_batch(
+        db: &DB,
+        batch: &WriteBatchWithIndex,
+        cf_handle: Option<&ColumnFamily>,
+        readopts: ReadOptions,
+    ) -> DBRawIterator<'a> {
+        unsafe {
+            // The batch iterator takes ownership of the base iterator.
+            let inner = match cf_handle {
+                None => ffi::rocksdb_writebatch_wi_create_iterator_with_base(
+                    batch.inner,
+                    ffi::rocksdb_create_iterator(db.inner, readopts.inner),
+                ),
+                Some(cf) => ffi::rocksdb_writebatch_wi_create_iterator_with_base_cf(
+                    batch.inner,
+                    ffi::rocksdb_create_iterator_cf(db.inner, readopts.inner, cf.inner),
+                    cf.inner,
+                ),
+            };
+            DBRawIterator {
+                inner,
+                _readopts: readopts,
+                db: PhantomData,
+            }
+        }
+    }
+
     /// Returns `true` if the iterator is valid. An iterator is invalidated when
     /// it reaches the end of its defined range, or when it encounters an error.
     ///
@@ -274,6 +303,41 @@ impl<'a> DBRawIterator<'a> {
         }
     }
 
+    /// Makes the iterator read from the latest state of the database, so that it sees
+    /// the writes made since it was created and stops pinning the memtables and SST
+    /// files it was reading from.
+    ///
+    /// The iterator is left unpositioned and must be seeked before use. Tailing
+    /// iterators and iterators created with a snapshot don't support refreshing and
+    /// return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocksdb::{DB, Options};
+    ///
+    /// let path = "_path_for_rocksdb_storage_refresh";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     let mut iter = db.raw_iterator();
+    ///
+    ///     db.put(b"k1", b"v1").unwrap();
+    ///     iter.seek_to_first();
+    ///     assert!(!iter.valid());
+    ///
+    ///     iter.refresh().unwrap();
+    ///     iter.seek_to_first();
+    ///     assert_eq!(iter.key(), Some(&b"k1"[..]));
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_iter_refresh(self.inner));
+        }
+        Ok(())
+    }
+
     /// Seeks to the next key.
     pub fn next(&mut self) {
         unsafe {
@@ -321,6 +385,124 @@ impl<'a> DBRawIterator<'a> {
             None
         }
     }
+
+    /// Reads up to `n` entries into `batch`, starting at the current entry and
+    /// moving forward, and returns the number of entries read. Afterwards the
+    /// iterator is positioned at the first entry that was not read.
+    ///
+    /// This is faster than calling `key`, `value` and `next` for every entry,
+    /// especially when `batch` is reused between calls.
+    ///
+    /// ```
+    /// use rocksdb::{DB, IterBatch, Options};
+    ///
+    /// let path = "_path_for_rocksdb_storage_batched";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     db.put(b"k1", b"v1").unwrap();
+    ///
+    ///     let mut iter = db.raw_iterator();
+    ///     iter.seek_to_first();
+    ///     let mut batch = IterBatch::default();
+    ///     while iter.next_batch(&mut batch, 1024) > 0 {
+    ///         for (key, value) in batch.iter() {
+    ///             println!("Saw {:?} {:?}", key, value);
+    ///         }
+    ///     }
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn next_batch(&mut self, batch: &mut IterBatch, n: usize) -> usize {
+        self.read_batch(batch, n, false)
+    }
+
+    /// Like [`next_batch`](#method.next_batch), but moves backward.
+    pub fn prev_batch(&mut self, batch: &mut IterBatch, n: usize) -> usize {
+        self.read_batch(batch, n, true)
+    }
+
+    fn read_batch(&mut self, batch: &mut IterBatch, n: usize, reverse: bool) -> usize {
+        batch.clear();
+        unsafe {
+            ffi::rocksdb_ext_iter_next_batch(
+                self.inner,
+                n as size_t,
+                reverse as c_uchar,
+                batch as *mut IterBatch as *mut c_void,
+                Some(iter_batch_push_callback),
+            ) as usize
+        }
+    }
+}
+
+/// A reusable buffer for the entries read by
+/// [`DBRawIterator::next_batch`](struct.DBRawIterator.html#method.next_batch).
+///
+/// The keys and values of all entries share one buffer, so reading a batch
+/// does not allocate once the buffer is large enough.
+#[derive(Debug, Default, Clone)]
+pub struct IterBatch {
+    data: Vec<u8>,
+    // (offset of the key in `data`, key length, value length); the value follows the key.
+    entries: Vec<(usize, usize, usize)>,
+}
+
+impl IterBatch {
+    pub fn new() -> IterBatch {
+        IterBatch::default()
+    }
+
+    /// Returns the number of entries in the batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all entries, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.entries.clear();
+    }
+
+    /// Returns the key and value of the entry at `index`.
+    pub fn get(&self, index: usize) -> Option<(&[u8], &[u8])> {
+        self.entries.get(index).map(|&(offset, key_len, val_len)| {
+            let value_offset = offset + key_len;
+            (
+                &self.data[offset..value_offset],
+                &self.data[value_offset..value_offset + val_len],
+            )
+        })
+    }
+
+    /// Returns an iterator over the keys and values of the batch, in the
+    /// order they were read.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        (0..self.len()).map(move |i| self.get(i).unwrap())
+    }
+
+    fn push(&mut self, key: &[u8], value: &[u8]) {
+        self.entries.push((self.data.len(), key.len(), value.len()));
+        self.data.extend_from_slice(key);
+        self.data.extend_from_slice(value);
+    }
+}
+
+unsafe extern "C" fn iter_batch_push_callback(
+    state: *mut c_void,
+    key: *const c_char,
+    key_len: size_t,
+    value: *const c_char,
+    value_len: size_t,
+) {
+    let batch = &mut *(state as *mut IterBatch);
+    batch.push(
+        slice::from_raw_parts(key as *const u8, key_len as usize),
+        slice::from_raw_parts(value as *const u8, value_len as usize),
+    );
 }
 
 impl<'a> Drop for DBRawIterator<'a> {
@@ -369,6 +551,13 @@ pub struct DBIterator<'a> {
     raw: DBRawIterator<'a>,
     direction: Direction,
     just_seeked: bool,
+    /// Whether `position` is tracked, which is only done for tailing iterators as it
+    /// copies every returned key.
+    track_position: bool,
+    /// The last returned key or, if nothing was returned since the last `set_mode`,
+    /// the key it seeked to. Used by `refresh` to resume iterating.
+    position: Option<Vec<u8>>,
+    position_returned: bool,
 }
 
 pub enum Direction {
@@ -386,10 +575,14 @@ pub enum IteratorMode<'a> {
 
 impl<'a> DBIterator<'a> {
     pub(crate) fn new(db: &DB, readopts: ReadOptions, mode: IteratorMode) -> DBIterator<'a> {
+        let track_position = readopts.tailing;
         let mut rv = DBIterator {
             raw: DBRawIterator::new(db, readopts),
             direction: Direction::Forward, // blown away by set_mode()
             just_seeked: false,
+            track_position,
+            position: None,
+            position_returned: false,
         };
         rv.set_mode(mode);
         rv
@@ -401,16 +594,21 @@ impl<'a> DBIterator<'a> {
         readopts: ReadOptions,
         mode: IteratorMode,
     ) -> DBIterator<'a> {
+        let track_position = readopts.tailing;
         let mut rv = DBIterator {
             raw: DBRawIterator::new_cf(db, cf_handle, readopts),
             direction: Direction::Forward, // blown away by set_mode()
             just_seeked: false,
+            track_position,
+            position: None,
+            position_returned: false,
         };
         rv.set_mode(mode);
         rv
     }
 
     pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.position = None;
         match mode {
             IteratorMode::Start => {
                 self.raw.seek_to_first();
@@ -423,14 +621,67 @@ impl<'a> DBIterator<'a> {
             IteratorMode::From(key, Direction::Forward) => {
                 self.raw.seek(key);
                 self.direction = Direction::Forward;
+                if self.track_position {
+                    self.position = Some(key.to_vec());
+                }
             }
             IteratorMode::From(key, Direction::Reverse) => {
                 self.raw.seek_for_prev(key);
                 self.direction = Direction::Reverse;
+                if self.track_position {
+                    self.position = Some(key.to_vec());
+                }
             }
         };
 
         self.just_seeked = true;
+        self.position_returned = false;
+    }
+
+    /// Seeks a tailing iterator (see
+    /// [`ReadOptions::set_tailing`](crate::ReadOptions::set_tailing)) again to where
+    /// it stopped. Tailing iterators see new writes whenever they seek, so the next
+    /// call to `next()` returns the entry written after the last returned one, if any,
+    /// and a consumer can keep reading a growing database once the iterator is
+    /// exhausted.
+    ///
+    /// Fails for other iterators, which can be refreshed with
+    /// [`DBRawIterator::refresh`] instead.
+    ///
+    /// ```
+    /// use rocksdb::{IteratorMode, ReadOptions, DB, Options};
+    ///
+    /// let path = "_path_for_rocksdb_storage_tailing";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     let mut opts = ReadOptions::default();
+    ///     opts.set_tailing(true);
+    ///     let mut iter = db.iterator_opt(IteratorMode::Start, opts);
+    ///     assert!(iter.next().is_none());
+    ///
+    ///     db.put(b"k1", b"v1").unwrap();
+    ///     iter.refresh().unwrap();
+    ///     assert_eq!(iter.next().unwrap().0.as_ref(), b"k1");
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        if !self.track_position {
+            return Err(Error::new(
+                "Only tailing iterators can be refreshed, see DBRawIterator::refresh".to_owned(),
+            ));
+        }
+        match (&self.position, &self.direction) {
+            (None, Direction::Forward) => self.raw.seek_to_first(),
+            (None, Direction::Reverse) => self.raw.seek_to_last(),
+            (Some(key), Direction::Forward) => self.raw.seek(key),
+            (Some(key), Direction::Reverse) => self.raw.seek_for_prev(key),
+        }
+
+        // Skip the last returned entry unless it was deleted in the meantime.
+        self.just_seeked = !(self.position_returned
+            && self.raw.key() == self.position.as_ref().map(Vec::as_slice));
+        Ok(())
     }
 
     /// See [`valid`](DBRawIterator::valid)
@@ -442,14 +693,19 @@ impl<'a> DBIterator<'a> {
     pub fn status(&self) -> Result<(), Error> {
         self.raw.status()
     }
-}
 
-impl<'a> Iterator for DBIterator<'a> {
-    type Item = KVBytes;
+    /// Turns this iterator into one over the keys only, which doesn't copy the values.
+    ///
+    /// RocksDB still reads the blocks containing the values, so this only saves the
+    /// allocations and copies of the values.
+    pub fn keys(self) -> DBKeysIterator<'a> {
+        DBKeysIterator { inner: self }
+    }
 
-    fn next(&mut self) -> Option<KVBytes> {
+    /// Moves to the next entry and returns whether it is valid.
+    fn advance(&mut self) -> bool {
         if !self.raw.valid() {
-            return None;
+            return false;
         }
 
         // Initial call to next() after seeking should not move the iterator
@@ -463,7 +719,26 @@ impl<'a> Iterator for DBIterator<'a> {
             }
         }
 
-        if self.raw.valid() {
+        match self.raw.key() {
+            Some(key) => {
+                if self.track_position {
+                    let position = self.position.get_or_insert_with(Vec::new);
+                    position.clear();
+                    position.extend_from_slice(key);
+                    self.position_returned = true;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a> Iterator for DBIterator<'a> {
+    type Item = KVBytes;
+
+    fn next(&mut self) -> Option<KVBytes> {
+        if self.advance() {
             // .key() and .value() only ever return None if valid == false, which we've just cheked
             Some((
                 Box::from(self.raw.key().unwrap()),
@@ -475,6 +750,31 @@ impl<'a> Iterator for DBIterator<'a> {
     }
 }
 
+/// An iterator over the keys of a database or column family, created by `DBIterator::keys`
+/// or `DB::keys_iterator`.
+pub struct DBKeysIterator<'a> {
+    inner: DBIterator<'a>,
+}
+
+impl<'a> DBKeysIterator<'a> {
+    /// See [`status`](DBRawIterator::status)
+    pub fn status(&self) -> Result<(), Error> {
+        self.inner.status()
+    }
+}
+
+impl<'a> Iterator for DBKeysIterator<'a> {
+    type Item = Box<[u8]>;
+
+    fn next(&mut self) -> Option<Box<[u8]>> {
+        if self.inner.advance() {
+            Some(Box::from(self.inner.raw.key().unwrap()))
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> Into<DBRawIterator<'a>> for DBIterator<'a> {
     fn into(self) -> DBRawIterator<'a> {
         self.raw
@@ -491,6 +791,10 @@ impl<'a> Into<DBRawIterator<'a>> for DBIterator<'a> {
 /// The iterator item type is a tuple of (`u64`, `WriteBatch`) where the first
 /// value is the sequence number of the associated write batch.
 ///
+/// The iterator also stops when it fails to read the WAL, e.g. on a corrupted
+/// record. Call [`status`](DBWALIterator::status) once it returned `None` to
+/// tell the end of the WAL apart from an error.
+///
 pub struct DBWALIterator {
     pub(crate) inner: *mut ffi::rocksdb_wal_iterator_t,
 }
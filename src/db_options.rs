@@ -14,19 +14,30 @@
 
 use std::ffi::{CStr, CString};
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
 
 use libc::{self, c_char, c_int, c_uchar, c_uint, c_void, size_t};
 
 use crate::{
-    compaction_filter::{self, filter_callback, CompactionFilterCallback, CompactionFilterFn},
+    compaction_filter::{
+        self, filter_callback, CompactionFilterCallback, CompactionFilterFactory,
+        CompactionFilterFactoryCallback, CompactionFilterFn, TtlCompactionFilter,
+    },
     comparator::{self, ComparatorCallback, CompareFn},
+    event_listener::{self, EventListener},
     ffi,
+    ffi_util::to_cpath,
+    logger::{self, Logger},
     merge_operator::{
-        self, full_merge_callback, partial_merge_callback, MergeFn, MergeOperatorCallback,
+        self, full_merge_callback, partial_merge_callback, BorrowingMergeFn, MergeFn, MergeFns,
+        MergeOperatorCallback,
     },
     slice_transform::SliceTransform,
-    Snapshot,
+    statistics::{Histogram, HistogramData, Ticker},
+    ColumnFamilyDescriptor, Error, Snapshot,
 };
 
 fn new_cache(capacity: size_t) -> *mut ffi::rocksdb_cache_t {
@@ -69,6 +80,8 @@ fn new_cache(capacity: size_t) -> *mut ffi::rocksdb_cache_t {
 /// ```
 pub struct Options {
     pub(crate) inner: *mut ffi::rocksdb_options_t,
+    pub(crate) env: Option<Env>,
+    pub(crate) cancel_background_work_on_drop: Option<bool>,
 }
 
 /// Optionally disable WAL or sync for this write.
@@ -124,14 +137,84 @@ pub struct FlushOptions {
     pub(crate) inner: *mut ffi::rocksdb_flushoptions_t,
 }
 
+/// A block cache that can be shared between column families and databases to
+/// cap their total memory usage.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{BlockBasedOptions, Cache, Options};
+///
+/// let cache = Cache::new_lru_cache(64 << 20);
+///
+/// let mut block_opts = BlockBasedOptions::default();
+/// block_opts.set_block_cache(&cache);
+///
+/// let mut opts = Options::default();
+/// opts.set_block_based_table_factory(&block_opts);
+/// ```
+pub struct Cache {
+    pub(crate) inner: *mut ffi::rocksdb_cache_t,
+}
+
+/// Caps the memory used by the memtables of all column families and
+/// databases it is set on through `Options::set_write_buffer_manager`.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{Cache, Options, WriteBufferManager};
+///
+/// let cache = Cache::new_lru_cache(256 << 20);
+/// let manager = WriteBufferManager::new_with_cache(64 << 20, &cache);
+///
+/// let mut opts = Options::default();
+/// opts.set_write_buffer_manager(&manager);
+/// ```
+pub struct WriteBufferManager {
+    pub(crate) inner: *mut ffi::rocksdb_ext_write_buffer_manager_t,
+}
+
+/// An environment that runs the background jobs of the databases it is set
+/// on through `Options::set_env`.
+///
+/// Cloning an `Env` is cheap; the clones share the same environment, and it
+/// is kept alive for as long as a database uses it.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{Env, Options};
+///
+/// let mut env = Env::new().unwrap();
+/// env.set_background_threads(4);
+/// env.set_high_priority_background_threads(2);
+///
+/// let mut opts = Options::default();
+/// opts.set_env(&env);
+/// ```
+#[derive(Clone)]
+pub struct Env(Arc<EnvWrapper>);
+
+struct EnvWrapper {
+    inner: *mut ffi::rocksdb_env_t,
+}
+
 /// For configuring block-based file storage.
 pub struct BlockBasedOptions {
     pub(crate) inner: *mut ffi::rocksdb_block_based_table_options_t,
 }
 
+/// For configuring cuckoo hash table file storage, see `Options::set_cuckoo_table_factory`.
+pub struct CuckooTableOptions {
+    pub(crate) inner: *mut ffi::rocksdb_cuckoo_table_options_t,
+}
+
 pub struct ReadOptions {
     pub(crate) inner: *mut ffi::rocksdb_readoptions_t,
     iterate_upper_bound: Option<Vec<u8>>,
+    iterate_lower_bound: Option<Vec<u8>>,
+    pub(crate) tailing: bool,
 }
 
 /// For configuring external files ingestion.
@@ -159,22 +242,107 @@ pub struct IngestExternalFileOptions {
     pub(crate) inner: *mut ffi::rocksdb_ingestexternalfileoptions_t,
 }
 
+/// For configuring manual compactions through `DB::compact_range_opt`.
+///
+/// # Examples
+///
+/// Compact everything down to the bottommost level after a bulk delete:
+///
+/// ```
+/// use rocksdb::{BottommostLevelCompaction, CompactOptions, DB, Options};
+///
+/// let path = "_path_for_rocksdb_storage_compact_opts";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///
+///     let mut compact_opts = CompactOptions::default();
+///     compact_opts.set_exclusive_manual_compaction(false);
+///     compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+///
+///     db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts);
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct CompactOptions {
+    pub(crate) inner: *mut ffi::rocksdb_compactoptions_t,
+}
+
+/// For configuring universal style compaction through
+/// `Options::set_universal_compaction_options`.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{DBCompactionStyle, Options, UniversalCompactOptions};
+///
+/// let mut uco = UniversalCompactOptions::default();
+/// uco.set_size_ratio(2);
+/// uco.set_max_merge_width(16);
+///
+/// let mut opts = Options::default();
+/// opts.set_compaction_style(DBCompactionStyle::Universal);
+/// opts.set_universal_compaction_options(&uco);
+/// ```
+pub struct UniversalCompactOptions {
+    pub(crate) inner: *mut ffi::rocksdb_universal_compaction_options_t,
+}
+
+/// For configuring FIFO style compaction through
+/// `Options::set_fifo_compaction_options`.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{DBCompactionStyle, FifoCompactOptions, Options};
+///
+/// let mut fifo_opts = FifoCompactOptions::default();
+/// fifo_opts.set_max_table_files_size(1 << 30);
+///
+/// let mut opts = Options::default();
+/// opts.set_compaction_style(DBCompactionStyle::Fifo);
+/// opts.set_fifo_compaction_options(&fifo_opts);
+/// ```
+pub struct FifoCompactOptions {
+    pub(crate) inner: *mut ffi::rocksdb_fifo_compaction_options_t,
+}
+
+/// For configuring how `DB::get_updates_since_opt` reads the WAL.
+pub struct WalReadOptions {
+    pub(crate) inner: *mut ffi::rocksdb_wal_readoptions_t,
+}
+
 // Safety note: auto-implementing Send on most db-related types is prevented by the inner FFI
 // pointer. In most cases, however, this pointer is Send-safe because it is never aliased and
 // rocksdb internally does not rely on thread-local information for its user-exposed types.
 unsafe impl Send for Options {}
 unsafe impl Send for WriteOptions {}
 unsafe impl Send for BlockBasedOptions {}
+unsafe impl Send for CuckooTableOptions {}
 unsafe impl Send for ReadOptions {}
 unsafe impl Send for IngestExternalFileOptions {}
+unsafe impl Send for Cache {}
+unsafe impl Send for CompactOptions {}
+unsafe impl Send for UniversalCompactOptions {}
+unsafe impl Send for FifoCompactOptions {}
+unsafe impl Send for WriteBufferManager {}
+unsafe impl Send for WalReadOptions {}
+unsafe impl Send for EnvWrapper {}
 
 // Sync is similarly safe for many types because they do not expose interior mutability, and their
 // use within the rocksdb library is generally behind a const reference
 unsafe impl Sync for Options {}
 unsafe impl Sync for WriteOptions {}
 unsafe impl Sync for BlockBasedOptions {}
+unsafe impl Sync for CuckooTableOptions {}
 unsafe impl Sync for ReadOptions {}
 unsafe impl Sync for IngestExternalFileOptions {}
+unsafe impl Sync for Cache {}
+unsafe impl Sync for CompactOptions {}
+unsafe impl Sync for UniversalCompactOptions {}
+unsafe impl Sync for FifoCompactOptions {}
+unsafe impl Sync for WriteBufferManager {}
+unsafe impl Sync for WalReadOptions {}
+unsafe impl Sync for EnvWrapper {}
 
 impl Drop for Options {
     fn drop(&mut self) {
@@ -184,6 +352,46 @@ impl Drop for Options {
     }
 }
 
+impl Drop for Cache {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_cache_destroy(self.inner);
+        }
+    }
+}
+
+impl Drop for UniversalCompactOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_destroy(self.inner);
+        }
+    }
+}
+
+impl Drop for FifoCompactOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_fifo_compaction_options_destroy(self.inner);
+        }
+    }
+}
+
+impl Drop for WriteBufferManager {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_ext_write_buffer_manager_destroy(self.inner);
+        }
+    }
+}
+
+impl Drop for EnvWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_env_destroy(self.inner);
+        }
+    }
+}
+
 impl Drop for BlockBasedOptions {
     fn drop(&mut self) {
         unsafe {
@@ -192,6 +400,14 @@ impl Drop for BlockBasedOptions {
     }
 }
 
+impl Drop for CuckooTableOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_destroy(self.inner);
+        }
+    }
+}
+
 impl Drop for FlushOptions {
     fn drop(&mut self) {
         unsafe {
@@ -220,6 +436,139 @@ impl Drop for IngestExternalFileOptions {
     }
 }
 
+impl Drop for CompactOptions {
+    fn drop(&mut self) {
+        unsafe { ffi::rocksdb_compactoptions_destroy(self.inner) }
+    }
+}
+
+impl Drop for WalReadOptions {
+    fn drop(&mut self) {
+        unsafe { ffi::rocksdb_ext_wal_readoptions_destroy(self.inner) }
+    }
+}
+
+impl Cache {
+    /// Creates an LRU cache with the given capacity in bytes.
+    pub fn new_lru_cache(capacity: size_t) -> Cache {
+        Cache {
+            inner: new_cache(capacity),
+        }
+    }
+
+    /// Returns the memory size of the entries residing in the cache.
+    pub fn get_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_cache_get_usage(self.inner) }
+    }
+
+    /// Returns the memory size of the entries pinned in the cache.
+    pub fn get_pinned_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_cache_get_pinned_usage(self.inner) }
+    }
+
+    /// Sets the maximum capacity of the cache. Entries are evicted if the
+    /// current usage exceeds the new capacity.
+    pub fn set_capacity(&mut self, capacity: size_t) {
+        unsafe {
+            ffi::rocksdb_cache_set_capacity(self.inner, capacity);
+        }
+    }
+}
+
+impl WriteBufferManager {
+    /// Creates a manager that flushes memtables once they use more than
+    /// `buffer_size` bytes in total.
+    pub fn new(buffer_size: size_t) -> WriteBufferManager {
+        WriteBufferManager {
+            inner: unsafe { ffi::rocksdb_ext_write_buffer_manager_create(buffer_size) },
+        }
+    }
+
+    /// Like `new`, but also charges the memtable memory to `cache`, so the
+    /// block cache and memtables share a single memory budget.
+    pub fn new_with_cache(buffer_size: size_t, cache: &Cache) -> WriteBufferManager {
+        WriteBufferManager {
+            inner: unsafe {
+                ffi::rocksdb_ext_write_buffer_manager_create_with_cache(buffer_size, cache.inner)
+            },
+        }
+    }
+
+    /// Returns the memory currently used by memtables.
+    pub fn get_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_ext_write_buffer_manager_memory_usage(self.inner) }
+    }
+
+    /// Returns the memory budget.
+    pub fn get_buffer_size(&self) -> usize {
+        unsafe { ffi::rocksdb_ext_write_buffer_manager_buffer_size(self.inner) }
+    }
+}
+
+impl Env {
+    /// Returns the default environment, which is shared with all databases
+    /// that don't have an `Env` set.
+    pub fn new() -> Result<Env, Error> {
+        Env::from_raw(unsafe { ffi::rocksdb_create_default_env() })
+    }
+
+    /// Returns a new environment that keeps all files in memory, e.g. for tests.
+    pub fn mem_env() -> Result<Env, Error> {
+        Env::from_raw(unsafe { ffi::rocksdb_create_mem_env() })
+    }
+
+    fn from_raw(inner: *mut ffi::rocksdb_env_t) -> Result<Env, Error> {
+        if inner.is_null() {
+            Err(Error::new("Could not create RocksDB env".to_owned()))
+        } else {
+            Ok(Env(Arc::new(EnvWrapper { inner })))
+        }
+    }
+
+    /// Sets the number of threads in the LOW priority thread pool, which runs
+    /// compactions.
+    ///
+    /// Default: `1`
+    pub fn set_background_threads(&mut self, num_threads: c_int) {
+        unsafe {
+            ffi::rocksdb_env_set_background_threads(self.0.inner, num_threads);
+        }
+    }
+
+    /// Sets the number of threads in the HIGH priority thread pool, which runs
+    /// flushes when `Options::set_max_background_flushes` is positive.
+    ///
+    /// Default: `1`
+    pub fn set_high_priority_background_threads(&mut self, num_threads: c_int) {
+        unsafe {
+            ffi::rocksdb_env_set_high_priority_background_threads(self.0.inner, num_threads);
+        }
+    }
+
+    /// Lowers the IO priority of the threads in the LOW priority thread pool.
+    /// Only has an effect on Linux.
+    pub fn lower_thread_pool_io_priority(&mut self) {
+        unsafe {
+            ffi::rocksdb_env_lower_thread_pool_io_priority(self.0.inner);
+        }
+    }
+
+    /// Lowers the IO priority of the threads in the HIGH priority thread pool.
+    /// Only has an effect on Linux.
+    pub fn lower_high_priority_thread_pool_io_priority(&mut self) {
+        unsafe {
+            ffi::rocksdb_env_lower_high_priority_thread_pool_io_priority(self.0.inner);
+        }
+    }
+
+    /// Waits for all threads started by the environment to finish.
+    pub fn join_all_threads(&mut self) {
+        unsafe {
+            ffi::rocksdb_env_join_all_threads(self.0.inner);
+        }
+    }
+}
+
 impl BlockBasedOptions {
     /// Approximate size of user data packed per block. Note that the
     /// block size specified here corresponds to uncompressed data. The
@@ -250,6 +599,23 @@ impl BlockBasedOptions {
     ///
     /// Use partitioned full filters for each SST file. This option is
     /// incompatible with block-based filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{BlockBasedIndexType, BlockBasedOptions, Options};
+    ///
+    /// let mut block_opts = BlockBasedOptions::default();
+    /// block_opts.set_index_type(BlockBasedIndexType::TwoLevelIndexSearch);
+    /// block_opts.set_bloom_filter(10, false);
+    /// block_opts.set_partition_filters(true);
+    /// block_opts.set_metadata_block_size(4096);
+    /// block_opts.set_cache_index_and_filter_blocks(true);
+    /// block_opts.set_pin_top_level_index_and_filter(true);
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_block_based_table_factory(&block_opts);
+    /// ```
     pub fn set_partition_filters(&mut self, size: bool) {
         unsafe {
             ffi::rocksdb_block_based_options_set_partition_filters(self.inner, size as c_uchar);
@@ -281,6 +647,22 @@ impl BlockBasedOptions {
         }
     }
 
+    /// Uses the given cache for blocks. The same cache can be set on the
+    /// options of several column families and databases, which then share
+    /// its capacity.
+    pub fn set_block_cache(&mut self, cache: &Cache) {
+        unsafe {
+            ffi::rocksdb_block_based_options_set_block_cache(self.inner, cache.inner);
+        }
+    }
+
+    /// Uses the given cache for compressed blocks.
+    pub fn set_block_cache_compressed(&mut self, cache: &Cache) {
+        unsafe {
+            ffi::rocksdb_block_based_options_set_block_cache_compressed(self.inner, cache.inner);
+        }
+    }
+
     pub fn disable_cache(&mut self) {
         unsafe {
             ffi::rocksdb_block_based_options_set_no_block_cache(self.inner, true as c_uchar);
@@ -299,6 +681,16 @@ impl BlockBasedOptions {
         }
     }
 
+    /// If true, place whole keys in the filter (not just prefixes).
+    /// This must generally be true for gets to be efficient.
+    ///
+    /// Default: true
+    pub fn set_whole_key_filtering(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_block_based_options_set_whole_key_filtering(self.inner, v as c_uchar);
+        }
+    }
+
     pub fn set_cache_index_and_filter_blocks(&mut self, v: bool) {
         unsafe {
             ffi::rocksdb_block_based_options_set_cache_index_and_filter_blocks(self.inner, v as u8);
@@ -436,7 +828,137 @@ impl Default for BlockBasedOptions {
     }
 }
 
+impl CuckooTableOptions {
+    /// Determines the utilization of hash tables. Smaller values
+    /// result in larger hash tables with fewer collisions.
+    ///
+    /// Default: 0.9
+    pub fn set_hash_ratio(&mut self, ratio: f64) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_set_hash_ratio(self.inner, ratio);
+        }
+    }
+
+    /// A property used by builder to determine the depth to go to
+    /// to search for a path to displace elements in case of
+    /// collision. See Builder.MakeSpaceForKey method. Higher
+    /// values result in more efficient hash tables with fewer
+    /// lookups but take more time to build.
+    ///
+    /// Default: 100
+    pub fn set_max_search_depth(&mut self, depth: u32) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_set_max_search_depth(self.inner, depth);
+        }
+    }
+
+    /// In case of collision while inserting, the builder
+    /// attempts to insert in the next cuckoo_block_size
+    /// locations before skipping over to the next Cuckoo hash
+    /// function. This makes lookups more cache friendly in case
+    /// of collisions.
+    ///
+    /// Default: 5
+    pub fn set_cuckoo_block_size(&mut self, size: u32) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_set_cuckoo_block_size(self.inner, size);
+        }
+    }
+
+    /// If this option is enabled, user key is treated as uint64_t and its value
+    /// is used as hash value directly. This option changes builder's behavior.
+    /// Reader ignore this option and behave according to what specified in
+    /// table property.
+    ///
+    /// Default: false
+    pub fn set_identity_as_first_hash(&mut self, flag: bool) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_set_identity_as_first_hash(self.inner, flag as c_uchar);
+        }
+    }
+
+    /// If this option is set to true, module is used during hash calculation.
+    /// This often yields better space efficiency at the cost of performance.
+    /// If this option is set to false, # of entries in table is constrained to
+    /// be power of two, and bit and is used to calculate hash, which is faster in general.
+    ///
+    /// Default: true
+    pub fn set_use_module_hash(&mut self, flag: bool) {
+        unsafe {
+            ffi::rocksdb_cuckoo_options_set_use_module_hash(self.inner, flag as c_uchar);
+        }
+    }
+}
+
+impl Default for CuckooTableOptions {
+    fn default() -> CuckooTableOptions {
+        let opts = unsafe { ffi::rocksdb_cuckoo_options_create() };
+        if opts.is_null() {
+            panic!("Could not create RocksDB cuckoo table options");
+        }
+        CuckooTableOptions { inner: opts }
+    }
+}
+
 impl Options {
+    /// Loads the options a database was last opened with from its OPTIONS
+    /// file, returning the database options and a descriptor for each of its
+    /// column families. They can be passed to `DB::open_cf_descriptors` to
+    /// reopen the database without knowing its column families upfront.
+    ///
+    /// Callbacks like comparators and merge operators are not persisted and
+    /// must be set again. If `ignore_unknown_options` is true, options written
+    /// by a newer RocksDB version are ignored instead of failing.
+    pub fn load_latest<P: AsRef<Path>>(
+        path: P,
+        ignore_unknown_options: bool,
+    ) -> Result<(Options, Vec<ColumnFamilyDescriptor>), Error> {
+        let cpath = to_cpath(path)?;
+        let mut db_options: *mut ffi::rocksdb_options_t = ptr::null_mut();
+        let mut num_column_families: size_t = 0;
+        let mut column_family_names: *mut *mut c_char = ptr::null_mut();
+        let mut column_family_options: *mut *mut ffi::rocksdb_options_t = ptr::null_mut();
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_load_latest_options(
+                cpath.as_ptr(),
+                ignore_unknown_options as c_uchar,
+                &mut db_options,
+                &mut num_column_families,
+                &mut column_family_names,
+                &mut column_family_options,
+            ));
+            let names = slice::from_raw_parts(column_family_names, num_column_families);
+            let options = slice::from_raw_parts(column_family_options, num_column_families);
+            let column_families = names
+                .iter()
+                .zip(options)
+                .map(|(name, options)| {
+                    ColumnFamilyDescriptor::new(
+                        CStr::from_ptr(*name).to_string_lossy().into_owned(),
+                        Options {
+                            inner: *options,
+                            env: None,
+                            cancel_background_work_on_drop: None,
+                        },
+                    )
+                })
+                .collect();
+            ffi::rocksdb_ext_load_latest_options_destroy(
+                column_family_names,
+                column_family_options,
+                num_column_families,
+            );
+            Ok((
+                Options {
+                    inner: db_options,
+                    env: None,
+                    cancel_background_work_on_drop: None,
+                },
+                column_families,
+            ))
+        }
+    }
+
     /// By default, RocksDB uses only one background thread for flush and
     /// compaction. Calling this function will set it up such that total of
     /// `total_threads` is used. Good value for `total_threads` is the number of
@@ -642,65 +1164,239 @@ impl Options {
         }
     }
 
-    /// If non-zero, we perform bigger reads when doing compaction. If you're
-    /// running RocksDB on spinning disks, you should set this to at least 2MB.
-    /// That way RocksDB's compaction is doing sequential instead of random reads.
+    /// Sets the maximum size of training data passed to zstd's dictionary
+    /// trainer. Using zstd's dictionary trainer can achieve even better
+    /// compression ratio improvements than using `max_dict_bytes` alone.
     ///
-    /// When non-zero, we also force new_table_reader_for_compaction_inputs to
-    /// true.
+    /// The training data will be used to generate a dictionary of
+    /// `max_dict_bytes`.
     ///
     /// Default: `0`
-    pub fn set_compaction_readahead_size(&mut self, compaction_readahead_size: usize) {
+    pub fn set_zstd_max_train_bytes(&mut self, value: c_int) {
         unsafe {
-            ffi::rocksdb_options_compaction_readahead_size(
-                self.inner,
-                compaction_readahead_size as usize,
-            );
+            ffi::rocksdb_options_set_compression_options_zstd_max_train_bytes(self.inner, value);
         }
     }
 
-    /// Allow RocksDB to pick dynamic base of bytes for levels.
-    /// With this feature turned on, RocksDB will automatically adjust max bytes for each level.
-    /// The goal of this feature is to have lower bound on size amplification.
+    /// Sets the compression algorithm used for the bottommost level, which
+    /// usually holds most of the data. It overrides `set_compression_type`
+    /// and `set_compression_per_level` for that level.
     ///
-    /// Default: false.
-    pub fn set_level_compaction_dynamic_level_bytes(&mut self, v: bool) {
+    /// Default: the compression of the other levels is used
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Options, DBCompressionType};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_compression_type(DBCompressionType::Lz4);
+    /// opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+    /// ```
+    pub fn set_bottommost_compression_type(&mut self, t: DBCompressionType) {
         unsafe {
-            ffi::rocksdb_options_set_level_compaction_dynamic_level_bytes(self.inner, v as c_uchar);
+            ffi::rocksdb_options_set_bottommost_compression(self.inner, t as c_int);
         }
     }
 
-    pub fn set_merge_operator(
-        &mut self,
-        name: &str,
-        full_merge_fn: MergeFn,
-        partial_merge_fn: Option<MergeFn>,
+    /// Different options for compression algorithms used by the bottommost
+    /// level, see `set_compression_options`. Setting them also enables them.
+    ///
+    /// Default: the options set by `set_compression_options` are used
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Options, DBCompressionType};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+    /// opts.set_bottommost_compression_options(-14, 3, 0, 16 * 1024);
+    /// opts.set_bottommost_zstd_max_train_bytes(100 * 16 * 1024);
+    /// ```
+    pub fn set_bottommost_compression_options(
+        &mut self,
+        w_bits: c_int,
+        level: c_int,
+        strategy: c_int,
+        max_dict_bytes: c_int,
+    ) {
+        unsafe {
+            ffi::rocksdb_options_set_bottommost_compression_options(
+                self.inner,
+                w_bits,
+                level,
+                strategy,
+                max_dict_bytes,
+                true as c_uchar,
+            );
+        }
+    }
+
+    /// Sets `zstd_max_train_bytes` for the bottommost level, see
+    /// `set_zstd_max_train_bytes`. Setting it also enables the bottommost
+    /// compression options.
+    ///
+    /// Default: `0`
+    pub fn set_bottommost_zstd_max_train_bytes(&mut self, value: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_bottommost_compression_options_zstd_max_train_bytes(
+                self.inner,
+                value,
+                true as c_uchar,
+            );
+        }
+    }
+
+    /// If non-zero, we perform bigger reads when doing compaction. If you're
+    /// running RocksDB on spinning disks, you should set this to at least 2MB.
+    /// That way RocksDB's compaction is doing sequential instead of random reads.
+    ///
+    /// When non-zero, we also force new_table_reader_for_compaction_inputs to
+    /// true.
+    ///
+    /// Default: `0`, or 2MB when
+    /// [`set_use_direct_io_for_flush_and_compaction`](#method.set_use_direct_io_for_flush_and_compaction)
+    /// is enabled
+    pub fn set_compaction_readahead_size(&mut self, compaction_readahead_size: usize) {
+        unsafe {
+            ffi::rocksdb_options_compaction_readahead_size(
+                self.inner,
+                compaction_readahead_size as usize,
+            );
+        }
+    }
+
+    /// Sets the maximum number of threads that will concurrently perform a
+    /// compaction job by breaking it into multiple, smaller ones that are run
+    /// simultaneously.
+    ///
+    /// Default: `1` (i.e. no subcompactions)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_subcompactions(4);
+    /// ```
+    pub fn set_max_subcompactions(&mut self, num: u32) {
+        unsafe {
+            ffi::rocksdb_options_set_max_subcompactions(self.inner, num);
+        }
+    }
+
+    /// Allow RocksDB to pick dynamic base of bytes for levels.
+    /// With this feature turned on, RocksDB will automatically adjust max bytes for each level.
+    /// The goal of this feature is to have lower bound on size amplification.
+    ///
+    /// Default: false.
+    pub fn set_level_compaction_dynamic_level_bytes(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_level_compaction_dynamic_level_bytes(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Sets the merge operator used by `DB::merge`.
+    ///
+    /// `full_merge_fn` combines the existing value (if any) with all merge
+    /// operands of a key. `partial_merge_fn`, if given, combines operands
+    /// without a base value and must be associative. Without it, operands are
+    /// kept until they can be fully merged. For associative operators the same
+    /// function can be passed as both.
+    pub fn set_merge_operator(
+        &mut self,
+        name: &str,
+        full_merge_fn: MergeFn,
+        partial_merge_fn: Option<MergeFn>,
+    ) {
+        self.set_merge_fns(
+            name,
+            MergeFns::Owned(full_merge_fn),
+            partial_merge_fn.map(MergeFns::Owned),
+            false,
+        );
+    }
+
+    /// Sets a merge operator whose functions can return one of their inputs, e.g. the
+    /// largest operand of a max operator, with `MergeValue::Borrowed` instead of
+    /// allocating a copy of it.
+    ///
+    /// See `set_merge_operator` for `full_merge_fn` and `partial_merge_fn`. If
+    /// `allow_single_operand` is true, `partial_merge_fn` is also called with a single
+    /// operand, e.g. to compact or normalize it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{MergeOperands, MergeValue, Options};
+    ///
+    /// fn max_merge<'a>(
+    ///     _key: &[u8],
+    ///     existing_val: Option<&'a [u8]>,
+    ///     operands: &'a mut MergeOperands,
+    /// ) -> Option<MergeValue<'a>> {
+    ///     existing_val.into_iter().chain(operands).max().map(MergeValue::Borrowed)
+    /// }
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_borrowing_merge_operator("max", max_merge, Some(max_merge), false);
+    /// ```
+    pub fn set_borrowing_merge_operator(
+        &mut self,
+        name: &str,
+        full_merge_fn: BorrowingMergeFn,
+        partial_merge_fn: Option<BorrowingMergeFn>,
+        allow_single_operand: bool,
+    ) {
+        self.set_merge_fns(
+            name,
+            MergeFns::Borrowing(full_merge_fn),
+            partial_merge_fn.map(MergeFns::Borrowing),
+            allow_single_operand,
+        );
+    }
+
+    fn set_merge_fns(
+        &mut self,
+        name: &str,
+        full_merge_fn: MergeFns,
+        partial_merge_fn: Option<MergeFns>,
+        allow_single_operand: bool,
     ) {
+        let has_partial_merge = partial_merge_fn.is_some();
         let cb = Box::new(MergeOperatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             full_merge_fn,
-            partial_merge_fn: partial_merge_fn.unwrap_or(full_merge_fn),
+            partial_merge_fn,
         });
 
         unsafe {
-            let mo = ffi::rocksdb_mergeoperator_create(
-                mem::transmute(cb),
+            ffi::rocksdb_ext_options_set_merge_operator(
+                self.inner,
+                Box::into_raw(cb) as *mut c_void,
                 Some(merge_operator::destructor_callback),
-                Some(full_merge_callback),
-                Some(partial_merge_callback),
-                None,
                 Some(merge_operator::name_callback),
+                Some(full_merge_callback),
+                if has_partial_merge {
+                    Some(partial_merge_callback)
+                } else {
+                    None
+                },
+                Some(merge_operator::delete_callback),
+                allow_single_operand as c_uchar,
             );
-            ffi::rocksdb_options_set_merge_operator(self.inner, mo);
         }
     }
 
+    /// Uses `merge_fn` for both full and partial merges, so it must be associative.
     #[deprecated(
         since = "0.5.0",
         note = "add_merge_operator has been renamed to set_merge_operator"
     )]
     pub fn add_merge_operator(&mut self, name: &str, merge_fn: MergeFn) {
-        self.set_merge_operator(name, merge_fn, None);
+        self.set_merge_operator(name, merge_fn, Some(merge_fn));
     }
 
     /// Sets a compaction filter used to determine if entries should be kept, changed,
@@ -733,6 +1429,91 @@ impl Options {
         }
     }
 
+    /// Sets a `TtlCompactionFilter` as the compaction filter.
+    pub fn set_ttl_compaction_filter(&mut self, filter: TtlCompactionFilter) {
+        self.set_compaction_filter(
+            "TtlCompactionFilter",
+            move |level: u32, key: &[u8], value: &[u8]| filter.filter(level, key, value),
+        );
+    }
+
+    /// Sets a compaction filter factory, which creates a new compaction filter
+    /// (see `set_compaction_filter`) for each compaction.
+    ///
+    /// The factory is told whether the compaction is full and whether it was
+    /// triggered manually, so it can e.g. only expire entries in manual
+    /// compactions. Filters created by the factory are named `name`.
+    ///
+    /// If multi-threaded compaction is used, `create` may be called multiple times
+    /// simultaneously, so the factory must be `Sync`.
+    pub fn set_compaction_filter_factory<F>(&mut self, name: &str, factory: F)
+    where
+        F: CompactionFilterFactory + Send + Sync + 'static,
+    {
+        let cb = Box::new(CompactionFilterFactoryCallback {
+            name: CString::new(name.as_bytes()).unwrap(),
+            factory,
+        });
+
+        unsafe {
+            let cff = ffi::rocksdb_compactionfilterfactory_create(
+                mem::transmute(cb),
+                Some(compaction_filter::factory_destructor_callback::<F>),
+                Some(compaction_filter::create_filter_callback::<F>),
+                Some(compaction_filter::factory_name_callback::<F>),
+            );
+            ffi::rocksdb_options_set_compaction_filter_factory(self.inner, cff);
+        }
+    }
+
+    /// Adds a listener for flush, compaction, write stall and background error
+    /// events of the databases opened with these options.
+    ///
+    /// See the [event_listener](event_listener/index.html) module for an example.
+    pub fn add_event_listener<L>(&mut self, listener: L)
+    where
+        L: EventListener + 'static,
+    {
+        let cb = Box::new(listener);
+
+        unsafe {
+            let listener = ffi::rocksdb_ext_eventlistener_create(
+                Box::into_raw(cb) as *mut c_void,
+                Some(event_listener::destructor_callback::<L>),
+                Some(event_listener::on_flush_completed_callback::<L>),
+                Some(event_listener::on_compaction_completed_callback::<L>),
+                Some(event_listener::on_stall_conditions_changed_callback::<L>),
+                Some(event_listener::on_background_error_callback::<L>),
+            );
+            ffi::rocksdb_ext_options_add_eventlistener(self.inner, listener);
+            ffi::rocksdb_ext_eventlistener_destroy(listener);
+        }
+    }
+
+    /// Sends the info log lines of at least `level` to `logger` instead of
+    /// writing them to a `LOG` file. This replaces
+    /// [`set_log_level`](#method.set_log_level), [`set_db_log_dir`](#method.set_db_log_dir)
+    /// and the other options of the `LOG` files.
+    ///
+    /// See the [logger](logger/index.html) module for an example.
+    pub fn set_logger<L>(&mut self, level: LogLevel, logger: L)
+    where
+        L: Logger + 'static,
+    {
+        let cb = Box::new(logger);
+
+        unsafe {
+            let logger = ffi::rocksdb_ext_logger_create(
+                Box::into_raw(cb) as *mut c_void,
+                Some(logger::destructor_callback::<L>),
+                level as c_int,
+                Some(logger::log_callback::<L>),
+            );
+            ffi::rocksdb_ext_options_set_info_log(self.inner, logger);
+            ffi::rocksdb_ext_logger_destroy(logger);
+        }
+    }
+
     /// Sets the comparator used to define the order of keys in the table.
     /// Default: a comparator that uses lexicographic byte-wise ordering
     ///
@@ -768,6 +1549,20 @@ impl Options {
         self.set_comparator(name, compare_fn);
     }
 
+    /// Optimizes for a workload of point lookups only, with no range scans.
+    ///
+    /// Internally, it sets a block-based table factory with a data block hash
+    /// index, bloom filters and a `cache_size` MB block cache, and enables the
+    /// memtable whole key bloom filter, so it overrides the table factory set before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.optimize_for_point_lookup(64);
+    /// ```
     pub fn optimize_for_point_lookup(&mut self, cache_size: u64) {
         unsafe {
             ffi::rocksdb_options_optimize_for_point_lookup(self.inner, cache_size);
@@ -817,6 +1612,25 @@ impl Options {
         }
     }
 
+    /// If max_open_files is -1, DB will open all files on DB::Open(). You can
+    /// use this option to increase the number of threads used to open the files.
+    ///
+    /// Default: `16`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_file_opening_threads(32);
+    /// ```
+    pub fn set_max_file_opening_threads(&mut self, nthreads: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_max_file_opening_threads(self.inner, nthreads);
+        }
+    }
+
     /// If true, then every store to stable storage will issue a fsync.
     /// If false, then every store to stable storage will issue a fdatasync.
     /// This parameter should be set to true while storing data to
@@ -836,6 +1650,28 @@ impl Options {
         unsafe { ffi::rocksdb_options_set_use_fsync(self.inner, useit as c_int) }
     }
 
+    /// If true, the implementation will do aggressive checking of the
+    /// data it is processing and will stop early if it detects any
+    /// errors. This may have unforeseen ramifications: for example, a
+    /// corruption of one DB entry may cause a large number of entries to
+    /// become unreadable or for the entire DB to become unopenable.
+    ///
+    /// Default: `true`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_paranoid_checks(false);
+    /// ```
+    pub fn set_paranoid_checks(&mut self, enabled: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_paranoid_checks(self.inner, enabled as c_uchar);
+        }
+    }
+
     /// Allows OS to incrementally sync files to disk while they are being
     /// written, asynchronously, in the background. This operation can be used
     /// to smooth out write I/Os over time. Users shouldn't rely on it for
@@ -864,6 +1700,24 @@ impl Options {
         }
     }
 
+    /// Same as bytes_per_sync, but applies to WAL files.
+    ///
+    /// Default: `0`, turned off
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_wal_bytes_per_sync(512 * 1024);
+    /// ```
+    pub fn set_wal_bytes_per_sync(&mut self, nbytes: u64) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_wal_bytes_per_sync(self.inner, nbytes);
+        }
+    }
+
     /// If true, allow multi-writers to update mem tables in parallel.
     /// Only some memtable_factory-s support concurrent writes; currently it
     /// is implemented only for SkipListFactory.  Concurrent memtable writes
@@ -895,6 +1749,9 @@ impl Options {
     /// buffered. The hardware buffer of the devices may however still
     /// be used. Memory mapped files are not impacted by these parameters.
     ///
+    /// Opening a database fails if this is enabled together with
+    /// [`set_allow_mmap_reads`](#method.set_allow_mmap_reads).
+    ///
     /// Default: false
     ///
     /// # Examples
@@ -919,6 +1776,11 @@ impl Options {
     /// be used. Memory mapped files are not impacted by these parameters.
     /// they may or may not improve performance depending on the use case
     ///
+    /// Compactions then read their inputs in chunks of
+    /// [`set_compaction_readahead_size`](#method.set_compaction_readahead_size)
+    /// bytes, which defaults to 2MB in this mode. Opening a database fails if this
+    /// is enabled together with [`set_allow_mmap_writes`](#method.set_allow_mmap_writes).
+    ///
     /// Default: false
     ///
     /// # Examples
@@ -1097,6 +1959,37 @@ impl Options {
         }
     }
 
+    /// Shares the memtable memory budget of `manager` with other column
+    /// families and databases, instead of `set_db_write_buffer_size`.
+    /// The options keep their own reference to the manager.
+    pub fn set_write_buffer_manager(&mut self, manager: &WriteBufferManager) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_write_buffer_manager(self.inner, manager.inner);
+        }
+    }
+
+    /// Uses `cache` to cache the values read by point lookups, in addition
+    /// to the block cache. The same cache can be set on the options of
+    /// several column families and databases, which then share its capacity.
+    /// The options keep their own reference to the cache.
+    ///
+    /// Default: no row cache
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Cache, Options};
+    ///
+    /// let cache = Cache::new_lru_cache(64 * 1024 * 1024);
+    /// let mut opts = Options::default();
+    /// opts.set_row_cache(&cache);
+    /// ```
+    pub fn set_row_cache(&mut self, cache: &Cache) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_row_cache(self.inner, cache.inner);
+        }
+    }
+
     /// Control maximum total data size for a level.
     /// max_bytes_for_level_base is the max total for level-1.
     /// Maximum number of bytes for level L can be calculated as
@@ -1185,6 +2078,27 @@ impl Options {
         }
     }
 
+    /// Sets the multiplier used to compute the target file size of each level
+    /// from [`set_target_file_size_base`](#method.set_target_file_size_base).
+    ///
+    /// Default: `1`, i.e. files of all levels have the same size
+    ///
+    /// Dynamically changeable through SetOptions() API
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_target_file_size_multiplier(2);
+    /// ```
+    pub fn set_target_file_size_multiplier(&mut self, mul: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_target_file_size_multiplier(self.inner, mul);
+        }
+    }
+
     /// Sets the minimum number of write buffers that will be merged together
     /// before writing to storage.  If set to `1`, then
     /// all write buffers are flushed to L0 as individual files and this increases
@@ -1290,6 +2204,22 @@ impl Options {
         }
     }
 
+    /// Sets the options needed to support Universal Style compactions.
+    /// Only used with `DBCompactionStyle::Universal`; the options are copied.
+    pub fn set_universal_compaction_options(&mut self, uco: &UniversalCompactOptions) {
+        unsafe {
+            ffi::rocksdb_options_set_universal_compaction_options(self.inner, uco.inner);
+        }
+    }
+
+    /// Sets the options for FIFO compaction style.
+    /// Only used with `DBCompactionStyle::Fifo`; the options are copied.
+    pub fn set_fifo_compaction_options(&mut self, fco: &FifoCompactOptions) {
+        unsafe {
+            ffi::rocksdb_options_set_fifo_compaction_options(self.inner, fco.inner);
+        }
+    }
+
     /// Sets the maximum number of concurrent background compaction jobs, submitted to
     /// the default LOW priority thread pool.
     /// We first try to schedule compactions based on
@@ -1348,6 +2278,56 @@ impl Options {
         }
     }
 
+    /// Sets the maximum number of concurrent background jobs (compactions and
+    /// flushes). RocksDB splits them between flushes and compactions by itself,
+    /// which is simpler than tuning `set_max_background_compactions` and
+    /// `set_max_background_flushes` separately.
+    ///
+    /// Default: `2`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_background_jobs(6);
+    /// ```
+    pub fn set_max_background_jobs(&mut self, jobs: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_max_background_jobs(self.inner, jobs);
+        }
+    }
+
+    /// Sets the environment used to run background jobs and access files.
+    /// The database keeps the `Env` alive for as long as it is open.
+    ///
+    /// Default: the default environment, see `Env::new`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Env, Options};
+    ///
+    /// let env = Env::mem_env().unwrap();
+    /// let mut opts = Options::default();
+    /// opts.set_env(&env);
+    /// ```
+    pub fn set_env(&mut self, env: &Env) {
+        unsafe {
+            ffi::rocksdb_options_set_env(self.inner, env.0.inner);
+        }
+        self.env = Some(env.clone());
+    }
+
+    /// Makes the database call `DB::cancel_all_background_work(wait)` when it is dropped,
+    /// so that closing it doesn't wait for pending compactions.
+    ///
+    /// Default: disabled
+    pub fn set_cancel_all_background_work_on_drop(&mut self, wait: bool) {
+        self.cancel_background_work_on_drop = Some(wait);
+    }
+
     /// Disables automatic compactions. Manual compactions can still
     /// be issued on this column family
     ///
@@ -1446,7 +2426,34 @@ impl Options {
         }
     }
 
-    // This is a factory that provides TableFactory objects.
+    /// Sets the table factory to a cuckoo hash table, which is designed for fast
+    /// point lookups in a fully in-memory database. It doesn't support
+    /// iteration in sorted order nor snapshots.
+    /// See official [wiki](https://github.com/facebook/rocksdb/wiki/CuckooTable-Format) for more
+    /// information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{CuckooTableOptions, Options};
+    ///
+    /// let mut opts = Options::default();
+    /// let mut factory_opts = CuckooTableOptions::default();
+    /// factory_opts.set_hash_ratio(0.8);
+    /// factory_opts.set_max_search_depth(20);
+    /// factory_opts.set_cuckoo_block_size(10);
+    /// factory_opts.set_identity_as_first_hash(true);
+    /// factory_opts.set_use_module_hash(false);
+    ///
+    /// opts.set_cuckoo_table_factory(&factory_opts);
+    /// ```
+    pub fn set_cuckoo_table_factory(&mut self, factory: &CuckooTableOptions) {
+        unsafe {
+            ffi::rocksdb_options_set_cuckoo_table_factory(self.inner, factory.inner);
+        }
+    }
+
+    // This is a factory that provides TableFactory objects.
     // Default: a block-based table factory that provides a default
     // implementation of TableBuilder and TableReader with default
     // BlockBasedTableOptions.
@@ -1538,6 +2545,49 @@ impl Options {
         }
     }
 
+    /// Sets how long archived WAL files are kept, e.g. so that `DB::get_updates_since` can
+    /// still read them. Together with `set_wal_size_limit_mb`:
+    ///
+    /// - If both are 0, WAL files are deleted as soon as possible and not archived.
+    /// - If only one is 0, archived files are deleted according to the other one.
+    /// - If both are set, archived files are deleted once they are older than the TTL or
+    ///   the oldest ones once the archive exceeds the size limit.
+    ///
+    /// Default: `0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// // Keep archived WAL files for a day.
+    /// opts.set_wal_ttl_seconds(24 * 60 * 60);
+    /// ```
+    pub fn set_wal_ttl_seconds(&mut self, secs: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_WAL_ttl_seconds(self.inner, secs);
+        }
+    }
+
+    /// Sets the maximum size of the archived WAL files, see `set_wal_ttl_seconds`.
+    ///
+    /// Default: `0`
+    pub fn set_wal_size_limit_mb(&mut self, size: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_WAL_size_limit_MB(self.inner, size);
+        }
+    }
+
+    /// Number of bytes to preallocate (via fallocate) the manifest files.
+    ///
+    /// Default: `4 MiB`
+    pub fn set_manifest_preallocation_size(&mut self, size: usize) {
+        unsafe {
+            ffi::rocksdb_options_set_manifest_preallocation_size(self.inner, size);
+        }
+    }
+
     pub fn enable_statistics(&mut self) {
         unsafe {
             ffi::rocksdb_options_enable_statistics(self.inner);
@@ -1558,8 +2608,63 @@ impl Options {
         }
     }
 
+    /// Returns the current count of `ticker`, or `0` if statistics are not enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{Options, Ticker};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.enable_statistics();
+    /// assert_eq!(opts.get_ticker_count(Ticker::BlockCacheMiss), 0);
+    /// ```
+    pub fn get_ticker_count(&self, ticker: Ticker) -> u64 {
+        let name = ticker.name();
+        unsafe {
+            ffi::rocksdb_ext_options_statistics_get_ticker_count(
+                self.inner,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+            )
+        }
+    }
+
+    /// Returns the current data of `histogram`, or `None` if statistics are not enabled.
+    pub fn get_histogram_data(&self, histogram: Histogram) -> Option<HistogramData> {
+        let name = histogram.name();
+        unsafe {
+            let mut data: ffi::rocksdb_ext_histogram_data_t = mem::zeroed();
+            let found = ffi::rocksdb_ext_options_statistics_get_histogram_data(
+                self.inner,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+                &mut data,
+            );
+            if found == 0 {
+                return None;
+            }
+            Some(HistogramData {
+                median: data.median,
+                p95: data.percentile95,
+                p99: data.percentile99,
+                average: data.average,
+                std_dev: data.standard_deviation,
+                max: data.max,
+                min: data.min,
+                count: data.count,
+                sum: data.sum,
+            })
+        }
+    }
+
     /// If not zero, dump `rocksdb.stats` to LOG every `stats_dump_period_sec`.
     ///
+    /// The dump is written as a single message at `LogLevel::Info`, so it can be
+    /// received in Rust through [`set_logger`](#method.set_logger). The same
+    /// text can also be read at any time from the
+    /// [`STATS`](properties/constant.STATS.html) property.
+    ///
     /// Default: `600` (10 mins)
     ///
     /// # Examples
@@ -1576,6 +2681,27 @@ impl Options {
         }
     }
 
+    /// If not zero, save a snapshot of the statistics enabled by
+    /// [`enable_statistics`](#method.enable_statistics) to RocksDB's in-memory
+    /// stats history every `stats_persist_period_sec`.
+    ///
+    /// Default: `600` (10 mins)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.enable_statistics();
+    /// opts.set_stats_persist_period_sec(60);
+    /// ```
+    pub fn set_stats_persist_period_sec(&mut self, period: c_uint) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_stats_persist_period_sec(self.inner, period);
+        }
+    }
+
     /// When set to true, reading SST files will opt out of the filesystem's
     /// readahead. Setting this to false may improve sequential iteration
     /// performance.
@@ -1614,6 +2740,30 @@ impl Options {
         }
     }
 
+    /// Enables whole key bloom filter in memtable. Note this will only take effect
+    /// if memtable_prefix_bloom_ratio is not 0. Enabling whole key filtering
+    /// can potentially reduce CPU usage for point-look-ups.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_memtable_prefix_bloom_ratio(0.1);
+    /// opts.set_memtable_whole_key_filtering(true);
+    /// ```
+    pub fn set_memtable_whole_key_filtering(&mut self, whole_key_filter: bool) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_memtable_whole_key_filtering(
+                self.inner,
+                whole_key_filter as c_uchar,
+            );
+        }
+    }
+
     /// Specifies the absolute path of the directory the
     /// write-ahead log (WAL) should be written to.
     ///
@@ -1634,6 +2784,53 @@ impl Options {
         }
     }
 
+    /// Sets the directories SST files are placed in, along with the target size of each
+    /// directory. Newer data is placed in the first directories and older data is moved to
+    /// the later ones once the former are full, e.g. to keep the recent data on a fast disk.
+    ///
+    /// Default: empty, files are placed in the database directory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{DBPath, Options};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_db_paths(&[
+    ///     DBPath::new("/path/to/nvme", 100 << 30),
+    ///     DBPath::new("/path/to/hdd", 2 << 40),
+    /// ]);
+    /// ```
+    pub fn set_db_paths(&mut self, paths: &[DBPath]) {
+        let (cpaths, target_sizes) = db_paths_to_raw(paths);
+        let cpaths_ptrs: Vec<_> = cpaths.iter().map(|p| p.as_ptr()).collect();
+        unsafe {
+            ffi::rocksdb_ext_options_set_db_paths(
+                self.inner,
+                cpaths_ptrs.as_ptr(),
+                target_sizes.as_ptr(),
+                paths.len() as size_t,
+            );
+        }
+    }
+
+    /// Like `set_db_paths`, but for the SST files of a single column family. The directories
+    /// must not be shared with other column families.
+    ///
+    /// Default: empty, the paths set by `set_db_paths` are used
+    pub fn set_cf_paths(&mut self, paths: &[DBPath]) {
+        let (cpaths, target_sizes) = db_paths_to_raw(paths);
+        let cpaths_ptrs: Vec<_> = cpaths.iter().map(|p| p.as_ptr()).collect();
+        unsafe {
+            ffi::rocksdb_ext_options_set_cf_paths(
+                self.inner,
+                cpaths_ptrs.as_ptr(),
+                target_sizes.as_ptr(),
+                paths.len() as size_t,
+            );
+        }
+    }
+
     /// If true, then DB::Open() will not update the statistics used to optimize
     /// compaction decision by loading table properties from many files.
     /// Turning off this feature will improve DBOpen time especially in disk environment.
@@ -1645,6 +2842,30 @@ impl Options {
         }
     }
 
+    /// If true, then DB::Open() will not fetch and check sizes of all sst files.
+    /// This may significantly speed up startup if there are many sst files,
+    /// especially when using non-default Env with expensive GetFileSize().
+    /// We'll still check that all required sst files exist.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_skip_checking_sst_file_sizes_on_db_open(true);
+    /// ```
+    pub fn set_skip_checking_sst_file_sizes_on_db_open(&mut self, skip: bool) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_skip_checking_sst_file_sizes_on_db_open(
+                self.inner,
+                skip as c_uchar,
+            );
+        }
+    }
+
     /// Specify the maximal number of info log files to be kept.
     ///
     /// Default: 1000
@@ -1722,6 +2943,26 @@ impl Options {
         }
     }
 
+    /// If true, WAL writes are buffered in memory and only written to the file
+    /// when `DB::flush_wal` is called (or the buffer is full). This reduces
+    /// the cost of small writes at the risk of losing them on a crash.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut options = Options::default();
+    /// options.set_manual_wal_flush(true);
+    /// ```
+    pub fn set_manual_wal_flush(&mut self, manual_wal_flush: bool) {
+        unsafe {
+            ffi::rocksdb_ext_options_set_manual_wal_flush(self.inner, manual_wal_flush as c_uchar);
+        }
+    }
+
     /// Use to control write rate of flush and compaction. Flush has higher
     /// priority than compaction.
     /// If rate limiter is enabled, bytes_per_sync is set to 1MB by default.
@@ -1751,6 +2992,45 @@ impl Options {
         }
     }
 
+    /// Sets the minimum level of the messages written to the info log.
+    ///
+    /// Default: `LogLevel::Info`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{LogLevel, Options};
+    ///
+    /// let mut options = Options::default();
+    /// options.set_log_level(LogLevel::Warn);
+    /// ```
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        unsafe {
+            ffi::rocksdb_options_set_info_log_level(self.inner, level as c_int);
+        }
+    }
+
+    /// Sets the directory of the info log files. If empty, they are written
+    /// to the database directory, otherwise their names are prefixed with the
+    /// absolute path of the database directory.
+    ///
+    /// Default: empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    ///
+    /// let mut options = Options::default();
+    /// options.set_db_log_dir("/var/log/rocksdb");
+    /// ```
+    pub fn set_db_log_dir<P: AsRef<Path>>(&mut self, path: P) {
+        let p = CString::new(path.as_ref().to_string_lossy().as_bytes()).unwrap();
+        unsafe {
+            ffi::rocksdb_options_set_db_log_dir(self.inner, p.as_ptr());
+        }
+    }
+
     /// Sets the maximal size of the info log file.
     ///
     /// If the log file is larger than `max_log_file_size`, a new info log file
@@ -1805,7 +3085,11 @@ impl Default for Options {
             if opts.is_null() {
                 panic!("Could not create RocksDB options");
             }
-            Options { inner: opts }
+            Options {
+                inner: opts,
+                env: None,
+                cancel_background_work_on_drop: None,
+            }
         }
     }
 }
@@ -1849,17 +3133,62 @@ impl WriteOptions {
         WriteOptions::default()
     }
 
+    /// If true, the write is flushed from the operating system buffer cache
+    /// before it is considered complete.
+    ///
+    /// Default: false
     pub fn set_sync(&mut self, sync: bool) {
         unsafe {
             ffi::rocksdb_writeoptions_set_sync(self.inner, sync as c_uchar);
         }
     }
 
+    /// If true, writes will not first go to the write ahead log, and the write
+    /// may get lost after a crash. Useful for bulk loads that can be retried.
+    ///
+    /// Default: false
     pub fn disable_wal(&mut self, disable: bool) {
         unsafe {
             ffi::rocksdb_writeoptions_disable_WAL(self.inner, disable as c_int);
         }
     }
+
+    /// If true and the user is trying to write to column families that don't exist
+    /// (they were dropped), ignore the write (don't return an error). If there
+    /// are multiple writes in a WriteBatch, other writes will succeed.
+    ///
+    /// Default: false
+    pub fn set_ignore_missing_column_families(&mut self, ignore: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_ignore_missing_column_families(
+                self.inner,
+                ignore as c_uchar,
+            );
+        }
+    }
+
+    /// If true and we need to wait or sleep for the write request, fails
+    /// immediately with `Status::Incomplete()`.
+    ///
+    /// Default: false
+    pub fn set_no_slowdown(&mut self, no_slowdown: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_no_slowdown(self.inner, no_slowdown as c_uchar);
+        }
+    }
+
+    /// If true, this write request is of lower priority if compaction is
+    /// behind. In this case, if `set_no_slowdown` is also true, the request
+    /// will be cancelled immediately with `Status::Incomplete()` returned.
+    /// Otherwise, it will be slowed down. The slowdown value is determined by
+    /// RocksDB to guarantee it introduces minimum impacts to high priority writes.
+    ///
+    /// Default: false
+    pub fn set_low_pri(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_low_pri(self.inner, v as c_uchar);
+        }
+    }
 }
 
 impl Default for WriteOptions {
@@ -1873,19 +3202,23 @@ impl Default for WriteOptions {
 }
 
 impl ReadOptions {
-    // TODO add snapshot setting here
-    // TODO add snapshot wrapper structs with proper destructors;
-    // that struct needs an "iterator" impl too.
-    #[allow(dead_code)]
-    fn fill_cache(&mut self, v: bool) {
+    /// Specify whether the data read for this iteration should be cached in memory.
+    /// Disable it for long scans, so they don't evict the blocks other reads use.
+    ///
+    /// Default: true
+    pub fn set_fill_cache(&mut self, v: bool) {
         unsafe {
             ffi::rocksdb_readoptions_set_fill_cache(self.inner, v as c_uchar);
         }
     }
 
     pub(crate) fn set_snapshot(&mut self, snapshot: &Snapshot) {
+        self.set_raw_snapshot(snapshot.inner);
+    }
+
+    pub(crate) fn set_raw_snapshot(&mut self, snapshot: *const ffi::rocksdb_snapshot_t) {
         unsafe {
-            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot.inner);
+            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot);
         }
     }
 
@@ -1907,10 +3240,37 @@ impl ReadOptions {
         }
     }
 
+    /// Sets the lower bound for an iterator.
+    /// The lower bound itself is included on the iteration result.
+    pub fn set_iterate_lower_bound<K: Into<Vec<u8>>>(&mut self, key: K) {
+        self.iterate_lower_bound = Some(key.into());
+        let lower_bound = self
+            .iterate_lower_bound
+            .as_ref()
+            .expect("iterate_lower_bound must exist.");
+
+        unsafe {
+            ffi::rocksdb_readoptions_set_iterate_lower_bound(
+                self.inner,
+                lower_bound.as_ptr() as *const c_char,
+                lower_bound.len() as size_t,
+            );
+        }
+    }
+
+    /// Enforces that the iterator only iterates over the same prefix as the seek.
+    /// This option is effective only for prefix seeks, i.e. when a prefix extractor
+    /// is set through `Options::set_prefix_extractor`.
+    ///
+    /// Default: `false`
     pub fn set_prefix_same_as_start(&mut self, v: bool) {
         unsafe { ffi::rocksdb_readoptions_set_prefix_same_as_start(self.inner, v as c_uchar) }
     }
 
+    /// Enables a total order seek regardless of the prefix extractor set through
+    /// `Options::set_prefix_extractor`, e.g. to iterate across prefixes.
+    ///
+    /// Default: `false`
     pub fn set_total_order_seek(&mut self, v: bool) {
         unsafe { ffi::rocksdb_readoptions_set_total_order_seek(self.inner, v as c_uchar) }
     }
@@ -1949,6 +3309,42 @@ impl ReadOptions {
         unsafe {
             ffi::rocksdb_readoptions_set_tailing(self.inner, v as c_uchar);
         }
+        self.tailing = v;
+    }
+
+    /// If true, the keys and values returned by an iterator stay valid for as
+    /// long as the iterator is not deleted. Requires the table format to
+    /// support it, like the block based table with `format_version` >= 1.
+    ///
+    /// Default: false
+    pub fn set_pin_data(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_pin_data(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If true, obsolete files are deleted in a background job when an
+    /// iterator is dropped, instead of in the thread dropping it.
+    ///
+    /// Default: false
+    pub fn set_background_purge_on_iterator_cleanup(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_background_purge_on_iterator_cleanup(
+                self.inner,
+                v as c_uchar,
+            );
+        }
+    }
+
+    /// If true, range deletions are not applied to the keys read, which is
+    /// faster but may return deleted keys. Only use it if the database
+    /// contains no range deletions.
+    ///
+    /// Default: false
+    pub fn set_ignore_range_deletions(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_ignore_range_deletions(self.inner, v as c_uchar);
+        }
     }
 }
 
@@ -1958,6 +3354,8 @@ impl Default for ReadOptions {
             ReadOptions {
                 inner: ffi::rocksdb_readoptions_create(),
                 iterate_upper_bound: None,
+                iterate_lower_bound: None,
+                tailing: false,
             }
         }
     }
@@ -2025,6 +3423,208 @@ impl Default for IngestExternalFileOptions {
     }
 }
 
+impl CompactOptions {
+    /// If true, no other compaction will run at the same time as this manual
+    /// compaction.
+    ///
+    /// Default: true
+    pub fn set_exclusive_manual_compaction(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_exclusive_manual_compaction(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Sets how the bottommost level is compacted.
+    ///
+    /// Default: `BottommostLevelCompaction::IfHaveCompactionFilter`
+    pub fn set_bottommost_level_compaction(&mut self, lvl: BottommostLevelCompaction) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_bottommost_level_compaction(self.inner, lvl as c_uchar);
+        }
+    }
+
+    /// If true, compacted files will be moved to the minimum level capable
+    /// of holding the data or to the level set by `set_target_level`.
+    ///
+    /// Default: false
+    pub fn set_change_level(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_change_level(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If change_level is true and target_level has a non-negative value, compacted
+    /// files will be moved to target_level.
+    ///
+    /// Default: -1
+    pub fn set_target_level(&mut self, lvl: c_int) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_target_level(self.inner, lvl);
+        }
+    }
+}
+
+impl Default for CompactOptions {
+    fn default() -> CompactOptions {
+        unsafe {
+            CompactOptions {
+                inner: ffi::rocksdb_compactoptions_create(),
+            }
+        }
+    }
+}
+
+impl UniversalCompactOptions {
+    /// Percentage flexibility while comparing file sizes. If the candidate
+    /// file(s) size is 1% smaller than the next file's size, then include
+    /// the next file into this candidate set.
+    ///
+    /// Default: `1`
+    pub fn set_size_ratio(&mut self, ratio: c_int) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_size_ratio(self.inner, ratio);
+        }
+    }
+
+    /// The minimum number of files in a single compaction run.
+    ///
+    /// Default: `2`
+    pub fn set_min_merge_width(&mut self, num: c_int) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_min_merge_width(self.inner, num);
+        }
+    }
+
+    /// The maximum number of files in a single compaction run.
+    ///
+    /// Default: `c_int::MAX`
+    pub fn set_max_merge_width(&mut self, num: c_int) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_max_merge_width(self.inner, num);
+        }
+    }
+
+    /// The size amplification is defined as the amount (in percentage) of
+    /// additional storage needed to store a single byte of data in the database.
+    /// For example, a size amplification of 2% means that a database that
+    /// contains 100 bytes of user-data may occupy up to 102 bytes of
+    /// physical storage.
+    ///
+    /// Default: `200`
+    pub fn set_max_size_amplification_percent(&mut self, v: c_int) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_max_size_amplification_percent(
+                self.inner, v,
+            );
+        }
+    }
+
+    /// If this option is set to be -1, all the output files
+    /// will follow compression type specified.
+    ///
+    /// If this option is not negative, we will try to make sure compressed
+    /// size is just above this value. In normal cases, at least this percentage
+    /// of data will be compressed.
+    ///
+    /// Default: `-1`
+    pub fn set_compression_size_percent(&mut self, v: c_int) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_compression_size_percent(self.inner, v);
+        }
+    }
+
+    /// The algorithm used to stop picking files into a single compaction run.
+    ///
+    /// Default: `UniversalCompactionStopStyle::Total`
+    pub fn set_stop_style(&mut self, style: UniversalCompactionStopStyle) {
+        unsafe {
+            ffi::rocksdb_universal_compaction_options_set_stop_style(self.inner, style as c_int);
+        }
+    }
+}
+
+impl Default for UniversalCompactOptions {
+    fn default() -> UniversalCompactOptions {
+        unsafe {
+            UniversalCompactOptions {
+                inner: ffi::rocksdb_universal_compaction_options_create(),
+            }
+        }
+    }
+}
+
+impl FifoCompactOptions {
+    /// Once the total size of the table files reaches this, the oldest table
+    /// file is deleted.
+    ///
+    /// Default: 1GB
+    pub fn set_max_table_files_size(&mut self, nbytes: u64) {
+        unsafe {
+            ffi::rocksdb_fifo_compaction_options_set_max_table_files_size(self.inner, nbytes);
+        }
+    }
+
+    /// If true, try to do compaction to compact smaller files into larger ones.
+    /// The minimum number of files to compact follows
+    /// `Options::set_level_zero_file_num_compaction_trigger`, and compaction
+    /// won't trigger if the average compacted bytes per deleted file is larger
+    /// than `Options::set_write_buffer_size`.
+    ///
+    /// Default: false
+    pub fn set_allow_compaction(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ext_fifo_compaction_options_set_allow_compaction(self.inner, v as c_uchar);
+        }
+    }
+}
+
+impl Default for FifoCompactOptions {
+    fn default() -> FifoCompactOptions {
+        unsafe {
+            FifoCompactOptions {
+                inner: ffi::rocksdb_fifo_compaction_options_create(),
+            }
+        }
+    }
+}
+
+impl WalReadOptions {
+    /// Sets whether the checksums of the WAL records are verified. A corrupted record
+    /// makes the iterator stop with an error reported by `DBWALIterator::status`.
+    ///
+    /// Default: true
+    pub fn set_verify_checksums(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ext_wal_readoptions_set_verify_checksums(self.inner, v as c_uchar);
+        }
+    }
+}
+
+impl Default for WalReadOptions {
+    fn default() -> WalReadOptions {
+        unsafe {
+            WalReadOptions {
+                inner: ffi::rocksdb_ext_wal_readoptions_create(),
+            }
+        }
+    }
+}
+
+/// Used by CompactOptions::set_bottommost_level_compaction.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BottommostLevelCompaction {
+    /// Skip bottommost level compaction.
+    Skip = 0,
+    /// Only compact the bottommost level if there is a compaction filter.
+    /// This is the default.
+    IfHaveCompactionFilter,
+    /// Always compact the bottommost level.
+    Force,
+    /// Always compact the bottommost level, but avoid rewriting files that
+    /// were created by this compaction.
+    ForceOptimized,
+}
+
 /// Used by BlockBasedOptions::set_index_type.
 pub enum BlockBasedIndexType {
     /// A space efficient index block that is optimized for
@@ -2066,6 +3666,40 @@ pub enum MemtableFactory {
     },
 }
 
+/// A directory SST files can be placed in, see `Options::set_db_paths`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DBPath {
+    path: PathBuf,
+    target_size: u64,
+}
+
+impl DBPath {
+    /// Creates a directory holding up to `target_size` bytes of SST files.
+    pub fn new<P: AsRef<Path>>(path: P, target_size: u64) -> DBPath {
+        DBPath {
+            path: path.as_ref().to_path_buf(),
+            target_size,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn target_size(&self) -> u64 {
+        self.target_size
+    }
+}
+
+fn db_paths_to_raw(paths: &[DBPath]) -> (Vec<CString>, Vec<u64>) {
+    let cpaths = paths
+        .iter()
+        .map(|p| CString::new(p.path.to_string_lossy().as_bytes()).unwrap())
+        .collect();
+    let target_sizes = paths.iter().map(|p| p.target_size).collect();
+    (cpaths, target_sizes)
+}
+
 /// Used with DBOptions::set_plain_table_factory.
 /// See official [wiki](https://github.com/facebook/rocksdb/wiki/PlainTable-Format) for more
 /// information.
@@ -2100,6 +3734,41 @@ pub enum DBCompactionStyle {
     Fifo = ffi::rocksdb_fifo_compaction as isize,
 }
 
+/// Used by UniversalCompactOptions::set_stop_style.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum UniversalCompactionStopStyle {
+    /// Pick files of similar size.
+    Similar = ffi::rocksdb_similar_size_compaction_stop_style as isize,
+    /// Pick files as long as their total size stays within the size ratio.
+    Total = ffi::rocksdb_total_size_compaction_stop_style as isize,
+}
+
+/// The severity of an info log message, used by `Options::set_log_level` and
+/// `Options::set_logger`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug = 0,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+    /// Messages written when a log file is opened, e.g. the options of the database.
+    Header,
+}
+
+impl LogLevel {
+    pub(crate) fn from_raw(level: c_int) -> LogLevel {
+        match level {
+            0 => LogLevel::Debug,
+            1 => LogLevel::Info,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Error,
+            4 => LogLevel::Fatal,
+            _ => LogLevel::Header,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DBRecoveryMode {
     TolerateCorruptedTailRecords = ffi::rocksdb_tolerate_corrupted_tail_records_recovery as isize,
@@ -2110,7 +3779,7 @@ pub enum DBRecoveryMode {
 
 #[cfg(test)]
 mod tests {
-    use crate::{MemtableFactory, Options};
+    use crate::{MemtableFactory, Options, WriteOptions};
 
     #[test]
     fn test_enable_statistics() {
@@ -2121,6 +3790,17 @@ mod tests {
 
         let opts = Options::default();
         assert!(opts.get_statistics().is_none());
+        assert!(opts.get_histogram_data(crate::Histogram::DbGet).is_none());
+    }
+
+    #[test]
+    fn test_write_options() {
+        let mut opts = WriteOptions::default();
+        opts.set_sync(false);
+        opts.disable_wal(true);
+        opts.set_ignore_missing_column_families(true);
+        opts.set_no_slowdown(true);
+        opts.set_low_pri(true);
     }
 
     #[test]
@@ -0,0 +1,218 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Callbacks for background events of a database.
+//!
+//! ```
+//! use rocksdb::{DB, Options};
+//! use rocksdb::event_listener::{EventListener, WriteStallCondition, WriteStallInfo};
+//!
+//! struct StallLogger;
+//!
+//! impl EventListener for StallLogger {
+//!     fn on_stall_conditions_changed(&self, info: &WriteStallInfo) {
+//!         if info.cur_condition != WriteStallCondition::Normal {
+//!             println!("writes to {} are {:?}", info.cf_name, info.cur_condition);
+//!         }
+//!     }
+//! }
+//!
+//! let path = "_rust_rocksdb_event_listener_doc";
+//! {
+//!     let mut opts = Options::default();
+//!     opts.create_if_missing(true);
+//!     opts.add_event_listener(StallLogger);
+//!     let db = DB::open(&opts, path).unwrap();
+//!     db.put(b"k1", b"v1").unwrap();
+//! }
+//! let _ = DB::destroy(&Options::default(), path);
+//! ```
+
+use crate::ffi;
+use libc::{c_char, c_int, c_void};
+use std::ffi::CStr;
+use std::slice;
+
+/// Receives the background events of the databases whose `Options` it was
+/// added to through `Options::add_event_listener`.
+///
+/// The methods are called from RocksDB's background threads and should
+/// return quickly, as they block the job that triggered them.
+pub trait EventListener: Send + Sync {
+    /// Called after a flush wrote a new SST file.
+    fn on_flush_completed(&self, _info: &FlushJobInfo) {}
+
+    /// Called after a compaction finished, successfully or not.
+    fn on_compaction_completed(&self, _info: &CompactionJobInfo) {}
+
+    /// Called when writes to a column family start or stop being delayed or
+    /// stopped.
+    fn on_stall_conditions_changed(&self, _info: &WriteStallInfo) {}
+
+    /// Called when a background job failed. The database stops accepting
+    /// writes until the error is resolved.
+    fn on_background_error(&self, _reason: BackgroundErrorReason, _error: &str) {}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlushJobInfo {
+    pub cf_name: String,
+    /// Path of the SST file the memtable was flushed to.
+    pub file_path: String,
+    pub job_id: i32,
+    /// Whether writes were slowed down when the flush started.
+    pub triggered_writes_slowdown: bool,
+    /// Whether writes were stopped when the flush started.
+    pub triggered_writes_stop: bool,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactionJobInfo {
+    pub cf_name: String,
+    /// The error of a failed compaction.
+    pub status: Option<String>,
+    pub job_id: i32,
+    pub base_input_level: i32,
+    pub output_level: i32,
+    pub elapsed_micros: u64,
+    pub num_input_files: u64,
+    pub num_output_files: u64,
+    pub num_input_records: u64,
+    pub num_output_records: u64,
+    pub total_input_bytes: u64,
+    pub total_output_bytes: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WriteStallCondition {
+    Normal,
+    Delayed,
+    Stopped,
+}
+
+impl WriteStallCondition {
+    fn from_raw(condition: c_int) -> WriteStallCondition {
+        match condition {
+            1 => WriteStallCondition::Delayed,
+            2 => WriteStallCondition::Stopped,
+            _ => WriteStallCondition::Normal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteStallInfo {
+    pub cf_name: String,
+    pub cur_condition: WriteStallCondition,
+    pub prev_condition: WriteStallCondition,
+}
+
+/// The kind of background job that failed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BackgroundErrorReason {
+    Flush,
+    Compaction,
+    WriteCallback,
+    MemTable,
+    Other,
+}
+
+impl BackgroundErrorReason {
+    fn from_raw(reason: c_int) -> BackgroundErrorReason {
+        match reason {
+            0 => BackgroundErrorReason::Flush,
+            1 => BackgroundErrorReason::Compaction,
+            2 => BackgroundErrorReason::WriteCallback,
+            3 => BackgroundErrorReason::MemTable,
+            _ => BackgroundErrorReason::Other,
+        }
+    }
+}
+
+unsafe fn to_string(ptr: *const c_char, len: usize) -> String {
+    String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len)).into_owned()
+}
+
+pub(crate) unsafe extern "C" fn destructor_callback<L: EventListener>(raw_cb: *mut c_void) {
+    let _: Box<L> = Box::from_raw(raw_cb as *mut L);
+}
+
+pub(crate) unsafe extern "C" fn on_flush_completed_callback<L: EventListener>(
+    raw_cb: *mut c_void,
+    info: *const ffi::rocksdb_ext_flush_job_info_t,
+) {
+    let listener = &*(raw_cb as *const L);
+    let info = &*info;
+    listener.on_flush_completed(&FlushJobInfo {
+        cf_name: to_string(info.cf_name, info.cf_name_len),
+        file_path: to_string(info.file_path, info.file_path_len),
+        job_id: info.job_id,
+        triggered_writes_slowdown: info.triggered_writes_slowdown != 0,
+        triggered_writes_stop: info.triggered_writes_stop != 0,
+        smallest_seqno: info.smallest_seqno,
+        largest_seqno: info.largest_seqno,
+    });
+}
+
+pub(crate) unsafe extern "C" fn on_compaction_completed_callback<L: EventListener>(
+    raw_cb: *mut c_void,
+    info: *const ffi::rocksdb_ext_compaction_job_info_t,
+) {
+    let listener = &*(raw_cb as *const L);
+    let info = &*info;
+    let status = if info.status.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(info.status).to_string_lossy().into_owned())
+    };
+    listener.on_compaction_completed(&CompactionJobInfo {
+        cf_name: to_string(info.cf_name, info.cf_name_len),
+        status,
+        job_id: info.job_id,
+        base_input_level: info.base_input_level,
+        output_level: info.output_level,
+        elapsed_micros: info.elapsed_micros,
+        num_input_files: info.num_input_files,
+        num_output_files: info.num_output_files,
+        num_input_records: info.num_input_records,
+        num_output_records: info.num_output_records,
+        total_input_bytes: info.total_input_bytes,
+        total_output_bytes: info.total_output_bytes,
+    });
+}
+
+pub(crate) unsafe extern "C" fn on_stall_conditions_changed_callback<L: EventListener>(
+    raw_cb: *mut c_void,
+    info: *const ffi::rocksdb_ext_write_stall_info_t,
+) {
+    let listener = &*(raw_cb as *const L);
+    let info = &*info;
+    listener.on_stall_conditions_changed(&WriteStallInfo {
+        cf_name: to_string(info.cf_name, info.cf_name_len),
+        cur_condition: WriteStallCondition::from_raw(info.cur_condition),
+        prev_condition: WriteStallCondition::from_raw(info.prev_condition),
+    });
+}
+
+pub(crate) unsafe extern "C" fn on_background_error_callback<L: EventListener>(
+    raw_cb: *mut c_void,
+    reason: c_int,
+    error: *const c_char,
+) {
+    let listener = &*(raw_cb as *const L);
+    let error = CStr::from_ptr(error).to_string_lossy();
+    listener.on_background_error(BackgroundErrorReason::from_raw(reason), &error);
+}
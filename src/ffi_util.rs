@@ -18,6 +18,7 @@ use libc::{self, c_char, c_void};
 use std::ffi::{CStr, CString};
 use std::path::Path;
 use std::ptr;
+use std::slice;
 
 pub fn error_message(ptr: *const c_char) -> String {
     let cstr = unsafe { CStr::from_ptr(ptr as *const _) };
@@ -35,6 +36,16 @@ pub fn opt_bytes_to_ptr<T: AsRef<[u8]>>(opt: Option<T>) -> *const c_char {
     }
 }
 
+/// Copies the `size` bytes at `ptr` into an owned vector, or returns `None` if
+/// `ptr` is null. The memory at `ptr` is not freed.
+pub(crate) unsafe fn raw_data(ptr: *const c_char, size: usize) -> Option<Vec<u8>> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(ptr as *const u8, size).to_vec())
+    }
+}
+
 pub(crate) fn to_cpath<P: AsRef<Path>>(path: P) -> Result<CString, Error> {
     match CString::new(path.as_ref().to_string_lossy().as_bytes()) {
         Ok(c) => Ok(c),
@@ -0,0 +1,192 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    db::prefix_bounds_readopts, db_iterator::KVBytes, ColumnFamily, DBIterator, Direction, Error,
+    IteratorMode, ReadOptions, WriteBatch, DB,
+};
+
+/// The keys of a database or column family starting with a given prefix, used as a
+/// separate key-value store.
+///
+/// The prefix is added to the keys passed in and removed from the keys returned, and
+/// iterators only return the keys of the keyspace. Keyspaces whose prefixes are
+/// prefixes of each other overlap, so fixed size prefixes are the safest choice.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{IteratorMode, Keyspace, Options, DB};
+///
+/// let path = "_path_for_rocksdb_storage_keyspace";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     let users = Keyspace::new(&db, b"usr:");
+///     let orders = Keyspace::new(&db, b"ord:");
+///     users.put(b"1", b"alice").unwrap();
+///     orders.put(b"1", b"book").unwrap();
+///
+///     assert_eq!(users.get(b"1").unwrap().unwrap(), b"alice");
+///     assert_eq!(db.get(b"ord:1").unwrap().unwrap(), b"book");
+///     assert_eq!(users.iterator(IteratorMode::Start).count(), 1);
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct Keyspace<'a> {
+    db: &'a DB,
+    cf: Option<&'a ColumnFamily>,
+    prefix: Vec<u8>,
+}
+
+impl<'a> Keyspace<'a> {
+    /// Creates the keyspace of the keys of `db` starting with `prefix`.
+    pub fn new<P: AsRef<[u8]>>(db: &'a DB, prefix: P) -> Keyspace<'a> {
+        Keyspace {
+            db,
+            cf: None,
+            prefix: prefix.as_ref().to_vec(),
+        }
+    }
+
+    /// Creates the keyspace of the keys of the column family `cf` starting with `prefix`.
+    pub fn new_cf<P: AsRef<[u8]>>(db: &'a DB, cf: &'a ColumnFamily, prefix: P) -> Keyspace<'a> {
+        Keyspace {
+            db,
+            cf: Some(cf),
+            prefix: prefix.as_ref().to_vec(),
+        }
+    }
+
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Returns the key stored in the database for `key`.
+    pub fn full_key<K: AsRef<[u8]>>(&self, key: K) -> Vec<u8> {
+        let key = key.as_ref();
+        let mut full_key = Vec::with_capacity(self.prefix.len() + key.len());
+        full_key.extend_from_slice(&self.prefix);
+        full_key.extend_from_slice(key);
+        full_key
+    }
+
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Result<Option<Vec<u8>>, Error> {
+        let key = self.full_key(key);
+        match self.cf {
+            Some(cf) => self.db.get_cf(cf, key),
+            None => self.db.get(key),
+        }
+    }
+
+    pub fn put<K, V>(&self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = self.full_key(key);
+        match self.cf {
+            Some(cf) => self.db.put_cf(cf, key, value),
+            None => self.db.put(key, value),
+        }
+    }
+
+    pub fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), Error> {
+        let key = self.full_key(key);
+        match self.cf {
+            Some(cf) => self.db.delete_cf(cf, key),
+            None => self.db.delete(key),
+        }
+    }
+
+    /// Adds a put of `key` in this keyspace to `batch`, so that writes to several
+    /// keyspaces can be committed atomically.
+    pub fn batch_put<K, V>(&self, batch: &mut WriteBatch, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = self.full_key(key);
+        match self.cf {
+            Some(cf) => batch.put_cf(cf, key, value),
+            None => batch.put(key, value),
+        }
+    }
+
+    /// Adds a delete of `key` in this keyspace to `batch`.
+    pub fn batch_delete<K: AsRef<[u8]>>(&self, batch: &mut WriteBatch, key: K) {
+        let key = self.full_key(key);
+        match self.cf {
+            Some(cf) => batch.delete_cf(cf, key),
+            None => batch.delete(key),
+        }
+    }
+
+    /// Creates an iterator over the keyspace. The keys passed in `mode` and the keys
+    /// returned don't include the prefix.
+    pub fn iterator(&self, mode: IteratorMode) -> KeyspaceIterator<'a> {
+        let opts = prefix_bounds_readopts(&self.prefix);
+        let inner = match mode {
+            IteratorMode::From(key, direction) => {
+                let key = self.full_key(key);
+                let mode = IteratorMode::From(&key, direction);
+                self.db_iterator(opts, mode)
+            }
+            mode => self.db_iterator(opts, mode),
+        };
+        KeyspaceIterator {
+            inner,
+            prefix_len: self.prefix.len(),
+        }
+    }
+
+    /// Creates an iterator over the keys of the keyspace starting with `prefix`, which
+    /// doesn't include the prefix of the keyspace.
+    pub fn prefix_iterator<P: AsRef<[u8]>>(&self, prefix: P) -> KeyspaceIterator<'a> {
+        let prefix = self.full_key(prefix);
+        let opts = prefix_bounds_readopts(&prefix);
+        KeyspaceIterator {
+            inner: self.db_iterator(opts, IteratorMode::From(&prefix, Direction::Forward)),
+            prefix_len: self.prefix.len(),
+        }
+    }
+
+    fn db_iterator(&self, opts: ReadOptions, mode: IteratorMode) -> DBIterator<'a> {
+        match self.cf {
+            Some(cf) => self.db.iterator_cf_opt(cf, opts, mode),
+            None => self.db.iterator_opt(mode, opts),
+        }
+    }
+}
+
+/// An iterator over a `Keyspace`, returning the keys without the prefix of the keyspace.
+pub struct KeyspaceIterator<'a> {
+    inner: DBIterator<'a>,
+    prefix_len: usize,
+}
+
+impl<'a> KeyspaceIterator<'a> {
+    /// See [`status`](crate::DBRawIterator::status)
+    pub fn status(&self) -> Result<(), Error> {
+        self.inner.status()
+    }
+}
+
+impl<'a> Iterator for KeyspaceIterator<'a> {
+    type Item = KVBytes;
+
+    fn next(&mut self) -> Option<KVBytes> {
+        let (key, value) = self.inner.next()?;
+        Some((Box::from(&key[self.prefix_len..]), value))
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{DBIterator, Error, IteratorMode, OwnedSnapshot, Snapshot, DB};
+
+/// Read access to a key-value store, usable as a trait object.
+///
+/// Implemented by `DB`, `Snapshot` and `OwnedSnapshot`, so code that only reads can accept
+/// any of them as `&dyn DynKvRead`.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{DynKvRead, DynKvStore, Options, DB};
+///
+/// fn count(store: &dyn DynKvRead) -> usize {
+///     store.iterator(rocksdb::IteratorMode::Start).count()
+/// }
+///
+/// let path = "_path_for_rocksdb_storage_dyn_kv_store";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     let store: &dyn DynKvStore = &db;
+///     store.put(b"k1", b"v1").unwrap();
+///     let snapshot = db.snapshot();
+///     store.delete(b"k1").unwrap();
+///     assert_eq!(count(&db), 0);
+///     assert_eq!(count(&snapshot), 1);
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub trait DynKvRead {
+    /// Returns the value of `key`, if any.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+
+    /// Creates an iterator over the store.
+    fn iterator(&self, mode: IteratorMode) -> DBIterator;
+}
+
+/// Read and write access to a key-value store, usable as a trait object.
+pub trait DynKvStore: DynKvRead {
+    /// Sets the value of `key`.
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Error>;
+
+    /// Removes `key`.
+    fn delete(&self, key: &[u8]) -> Result<(), Error>;
+}
+
+impl DynKvRead for DB {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        DB::get(self, key)
+    }
+
+    fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        DB::iterator(self, mode)
+    }
+}
+
+impl DynKvStore for DB {
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        DB::put(self, key, value)
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        DB::delete(self, key)
+    }
+}
+
+impl<'a> DynKvRead for Snapshot<'a> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Snapshot::get(self, key)
+    }
+
+    fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        Snapshot::iterator(self, mode)
+    }
+}
+
+impl DynKvRead for OwnedSnapshot {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        OwnedSnapshot::get(self, key)
+    }
+
+    fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        OwnedSnapshot::iterator(self, mode)
+    }
+}
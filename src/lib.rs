@@ -74,6 +74,9 @@
 #[macro_use]
 mod ffi_util;
 
+#[cfg(feature = "async")]
+mod async_db;
+
 pub mod backup;
 pub mod checkpoint;
 mod column_family;
@@ -83,28 +86,56 @@ mod db;
 mod db_iterator;
 mod db_options;
 mod db_pinnable_slice;
+pub mod event_listener;
+mod keyspace;
+mod kv_store;
+pub mod logger;
 pub mod merge_operator;
+mod perf;
+pub mod properties;
 mod slice_transform;
 mod snapshot;
 mod sst_file_writer;
+mod statistics;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod typed;
 mod write_batch;
+mod write_batch_with_index;
+mod write_coalescer;
 
+#[cfg(feature = "async")]
+pub use crate::async_db::AsyncDB;
 pub use crate::{
-    column_family::{ColumnFamily, ColumnFamilyDescriptor, DEFAULT_COLUMN_FAMILY_NAME},
+    column_family::{
+        BoundColumnFamily, ColumnFamily, ColumnFamilyDescriptor, DEFAULT_COLUMN_FAMILY_NAME,
+    },
     compaction_filter::Decision as CompactionDecision,
-    db::DB,
-    db_iterator::{DBIterator, DBRawIterator, DBWALIterator, Direction, IteratorMode},
+    db::{LiveFile, Range, DB},
+    db_iterator::{
+        DBIterator, DBKeysIterator, DBRawIterator, DBWALIterator, Direction, IterBatch,
+        IteratorMode,
+    },
     db_options::{
-        BlockBasedIndexType, BlockBasedOptions, DBCompactionStyle, DBCompressionType,
-        DBRecoveryMode, DataBlockIndexType, FlushOptions, IngestExternalFileOptions,
-        MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions, WriteOptions,
+        BlockBasedIndexType, BlockBasedOptions, BottommostLevelCompaction, Cache, CompactOptions,
+        CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath, DBRecoveryMode,
+        DataBlockIndexType, Env, FifoCompactOptions, FlushOptions, IngestExternalFileOptions,
+        LogLevel, MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions,
+        UniversalCompactOptions, UniversalCompactionStopStyle, WalReadOptions, WriteBufferManager,
+        WriteOptions,
     },
     db_pinnable_slice::DBPinnableSlice,
-    merge_operator::MergeOperands,
+    keyspace::{Keyspace, KeyspaceIterator},
+    kv_store::{DynKvRead, DynKvStore},
+    merge_operator::{MergeOperands, MergeValue},
+    perf::{set_perf_level, IOStatsContext, PerfContext, PerfLevel, PerfMetric},
     slice_transform::SliceTransform,
-    snapshot::Snapshot,
+    snapshot::{OwnedSnapshot, Snapshot},
     sst_file_writer::SstFileWriter,
-    write_batch::{WriteBatch, WriteBatchIterator},
+    statistics::{Histogram, HistogramData, Ticker},
+    write_batch::{BatchOp, WriteBatch, WriteBatchIterator, WriteBatchIteratorCf},
+    write_batch_with_index::WriteBatchWithIndex,
+    write_coalescer::WriteCoalescer,
 };
 
 use librocksdb_sys as ffi;
@@ -112,6 +143,28 @@ use librocksdb_sys as ffi;
 use std::error;
 use std::fmt;
 
+/// The kind of an `Error`, i.e. the code of the RocksDB status it was created from.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ErrorKind {
+    NotFound,
+    Corruption,
+    NotSupported,
+    InvalidArgument,
+    IOError,
+    MergeInProgress,
+    Incomplete,
+    ShutdownInProgress,
+    TimedOut,
+    Aborted,
+    Busy,
+    Expired,
+    TryAgain,
+    CompactionTooLarge,
+    ColumnFamilyDropped,
+    /// The error was not reported by RocksDB, e.g. an invalid argument detected by this crate.
+    Unknown,
+}
+
 /// A simple wrapper round a string, used for errors reported from
 /// ffi calls.
 #[derive(Debug, Clone, PartialEq)]
@@ -127,6 +180,48 @@ impl Error {
     pub fn into_string(self) -> String {
         self.into()
     }
+
+    /// Returns the kind of the error, parsed from the prefix RocksDB gives to the
+    /// messages of each status code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rocksdb::{ErrorKind, DB};
+    ///
+    /// let path = "_path_for_rocksdb_storage_error_kind";
+    /// {
+    ///     let _db = DB::open_default(path).unwrap();
+    ///     // The database is locked while it is open.
+    ///     let err = DB::open_default(path).unwrap_err();
+    ///     assert_eq!(err.kind(), ErrorKind::IOError);
+    /// }
+    /// let _ = DB::destroy(&rocksdb::Options::default(), path);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        // Same as the messages of Status::ToString in rocksdb/util/status.cc.
+        const PREFIXES: &[(&str, ErrorKind)] = &[
+            ("NotFound: ", ErrorKind::NotFound),
+            ("Corruption: ", ErrorKind::Corruption),
+            ("Not implemented: ", ErrorKind::NotSupported),
+            ("Invalid argument: ", ErrorKind::InvalidArgument),
+            ("IO error: ", ErrorKind::IOError),
+            ("Merge in progress: ", ErrorKind::MergeInProgress),
+            ("Result incomplete: ", ErrorKind::Incomplete),
+            ("Shutdown in progress: ", ErrorKind::ShutdownInProgress),
+            ("Operation timed out: ", ErrorKind::TimedOut),
+            ("Operation aborted: ", ErrorKind::Aborted),
+            ("Resource busy: ", ErrorKind::Busy),
+            ("Operation expired: ", ErrorKind::Expired),
+            ("Operation failed. Try again.: ", ErrorKind::TryAgain),
+            ("Compaction too large: ", ErrorKind::CompactionTooLarge),
+            ("Column family dropped: ", ErrorKind::ColumnFamilyDropped),
+        ];
+        PREFIXES
+            .iter()
+            .find(|(prefix, _)| self.message.starts_with(prefix))
+            .map_or(ErrorKind::Unknown, |(_, kind)| *kind)
+    }
 }
 
 impl AsRef<str> for Error {
@@ -156,9 +251,9 @@ impl fmt::Display for Error {
 #[cfg(test)]
 mod test {
     use super::{
-        BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, DBIterator, DBRawIterator,
-        IngestExternalFileOptions, Options, PlainTableFactoryOptions, ReadOptions, Snapshot,
-        SstFileWriter, WriteOptions, DB,
+        BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, CuckooTableOptions, DBIterator,
+        DBRawIterator, IngestExternalFileOptions, Options, PlainTableFactoryOptions, ReadOptions,
+        Snapshot, SstFileWriter, WriteCoalescer, WriteOptions, DB,
     };
 
     #[test]
@@ -179,10 +274,12 @@ mod test {
         is_send::<WriteOptions>();
         is_send::<IngestExternalFileOptions>();
         is_send::<BlockBasedOptions>();
+        is_send::<CuckooTableOptions>();
         is_send::<PlainTableFactoryOptions>();
         is_send::<ColumnFamilyDescriptor>();
         is_send::<ColumnFamily>();
         is_send::<SstFileWriter>();
+        is_send::<WriteCoalescer>();
     }
 
     #[test]
@@ -200,8 +297,10 @@ mod test {
         is_sync::<WriteOptions>();
         is_sync::<IngestExternalFileOptions>();
         is_sync::<BlockBasedOptions>();
+        is_sync::<CuckooTableOptions>();
         is_sync::<PlainTableFactoryOptions>();
         is_sync::<ColumnFamilyDescriptor>();
         is_sync::<SstFileWriter>();
+        is_sync::<WriteCoalescer>();
     }
 }
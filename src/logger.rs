@@ -0,0 +1,66 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receiving the info log of a database in Rust instead of a `LOG` file.
+//!
+//! ```
+//! use rocksdb::{DB, LogLevel, Options};
+//! use rocksdb::logger::Logger;
+//!
+//! struct StderrLogger;
+//!
+//! impl Logger for StderrLogger {
+//!     fn log(&self, level: LogLevel, message: &str) {
+//!         eprintln!("[rocksdb {:?}] {}", level, message);
+//!     }
+//! }
+//!
+//! let path = "_rust_rocksdb_logger_doc";
+//! {
+//!     let mut opts = Options::default();
+//!     opts.create_if_missing(true);
+//!     opts.set_logger(LogLevel::Warn, StderrLogger);
+//!     let db = DB::open(&opts, path).unwrap();
+//!     db.put(b"k1", b"v1").unwrap();
+//! }
+//! let _ = DB::destroy(&Options::default(), path);
+//! ```
+
+use crate::LogLevel;
+use libc::{c_char, c_int, c_void, size_t};
+use std::slice;
+
+/// Receives the info log lines of the databases whose `Options` it was set on
+/// through `Options::set_logger`.
+///
+/// The method is called from any thread, including the ones writing to the
+/// database, and should return quickly.
+pub trait Logger: Send + Sync {
+    fn log(&self, level: LogLevel, message: &str);
+}
+
+pub(crate) unsafe extern "C" fn destructor_callback<L: Logger>(raw_cb: *mut c_void) {
+    let _: Box<L> = Box::from_raw(raw_cb as *mut L);
+}
+
+pub(crate) unsafe extern "C" fn log_callback<L: Logger>(
+    raw_cb: *mut c_void,
+    level: c_int,
+    msg: *const c_char,
+    msg_len: size_t,
+) {
+    let logger = &*(raw_cb as *const L);
+    let msg = String::from_utf8_lossy(slice::from_raw_parts(msg as *const u8, msg_len));
+    logger.log(LogLevel::from_raw(level), msg.trim_end());
+}
@@ -55,7 +55,7 @@
 //!let _ = DB::destroy(&opts, path);
 //! ```
 
-use libc::{self, c_char, c_int, c_void, size_t};
+use libc::{c_char, c_int, c_void, size_t};
 use std::ffi::CString;
 use std::mem;
 use std::ptr;
@@ -63,10 +63,47 @@ use std::slice;
 
 pub type MergeFn = fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>>;
 
+/// A merge function that can return one of its inputs, see
+/// `Options::set_borrowing_merge_operator`.
+pub type BorrowingMergeFn =
+    for<'a> fn(&[u8], Option<&'a [u8]>, &'a mut MergeOperands) -> Option<MergeValue<'a>>;
+
+/// The result of a `BorrowingMergeFn`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeValue<'a> {
+    /// A new value.
+    Owned(Vec<u8>),
+    /// The existing value, an operand or a part of one of them, which RocksDB uses
+    /// without the merge operator allocating a copy, e.g. the largest operand of a max
+    /// operator.
+    Borrowed(&'a [u8]),
+}
+
+pub enum MergeFns {
+    Owned(MergeFn),
+    Borrowing(BorrowingMergeFn),
+}
+
+impl MergeFns {
+    fn call<'a>(
+        &self,
+        key: &[u8],
+        existing_value: Option<&'a [u8]>,
+        operands: &'a mut MergeOperands,
+    ) -> Option<MergeValue<'a>> {
+        match self {
+            MergeFns::Owned(merge_fn) => {
+                merge_fn(key, existing_value, operands).map(MergeValue::Owned)
+            }
+            MergeFns::Borrowing(merge_fn) => merge_fn(key, existing_value, operands),
+        }
+    }
+}
+
 pub struct MergeOperatorCallback {
     pub name: CString,
-    pub full_merge_fn: MergeFn,
-    pub partial_merge_fn: MergeFn,
+    pub full_merge_fn: MergeFns,
+    pub partial_merge_fn: Option<MergeFns>,
 }
 
 pub unsafe extern "C" fn destructor_callback(raw_cb: *mut c_void) {
@@ -74,7 +111,7 @@ pub unsafe extern "C" fn destructor_callback(raw_cb: *mut c_void) {
 }
 
 pub unsafe extern "C" fn name_callback(raw_cb: *mut c_void) -> *const c_char {
-    let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+    let cb = &*(raw_cb as *const MergeOperatorCallback);
     cb.name.as_ptr()
 }
 
@@ -88,9 +125,10 @@ pub unsafe extern "C" fn full_merge_callback(
     operands_list_len: *const size_t,
     num_operands: c_int,
     success: *mut u8,
+    borrowed_value: *mut *const c_char,
     new_value_length: *mut size_t,
 ) -> *mut c_char {
-    let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+    let cb = &*(raw_cb as *const MergeOperatorCallback);
     let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
     let oldval = if existing_value.is_null() {
@@ -101,21 +139,15 @@ pub unsafe extern "C" fn full_merge_callback(
             existing_value_len as usize,
         ))
     };
-    if let Some(mut result) = (cb.full_merge_fn)(key, oldval, operands) {
-        result.shrink_to_fit();
-        // TODO(tan) investigate zero-copy techniques to improve performance
-        let buf = libc::malloc(result.len() as size_t);
-        assert!(!buf.is_null());
-        *new_value_length = result.len() as size_t;
-        *success = 1 as u8;
-        ptr::copy(result.as_ptr() as *mut c_void, &mut *buf, result.len());
-        buf as *mut c_char
-    } else {
-        *success = 0 as u8;
-        ptr::null_mut() as *mut c_char
-    }
+    merge_result(
+        cb.full_merge_fn.call(key, oldval, operands),
+        success,
+        borrowed_value,
+        new_value_length,
+    )
 }
 
+/// Only registered if the operator has a partial merge function.
 pub unsafe extern "C" fn partial_merge_callback(
     raw_cb: *mut c_void,
     raw_key: *const c_char,
@@ -124,23 +156,64 @@ pub unsafe extern "C" fn partial_merge_callback(
     operands_list_len: *const size_t,
     num_operands: c_int,
     success: *mut u8,
+    borrowed_value: *mut *const c_char,
     new_value_length: *mut size_t,
 ) -> *mut c_char {
-    let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+    let cb = &*(raw_cb as *const MergeOperatorCallback);
+    let partial_merge_fn = match &cb.partial_merge_fn {
+        Some(partial_merge_fn) => partial_merge_fn,
+        None => return merge_result(None, success, borrowed_value, new_value_length),
+    };
     let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-    if let Some(mut result) = (cb.partial_merge_fn)(key, None, operands) {
-        result.shrink_to_fit();
-        // TODO(tan) investigate zero-copy techniques to improve performance
-        let buf = libc::malloc(result.len() as size_t);
-        assert!(!buf.is_null());
-        *new_value_length = result.len() as size_t;
-        *success = 1 as u8;
-        ptr::copy(result.as_ptr() as *mut c_void, &mut *buf, result.len());
-        buf as *mut c_char
-    } else {
-        *success = 0 as u8;
-        ptr::null_mut::<c_char>()
+    merge_result(
+        partial_merge_fn.call(key, None, operands),
+        success,
+        borrowed_value,
+        new_value_length,
+    )
+}
+
+/// Hands the merge result over to RocksDB without copying it. RocksDB copies
+/// owned values and then passes them back to `delete_callback`, and reads
+/// borrowed values from the inputs of the merge.
+unsafe fn merge_result(
+    result: Option<MergeValue>,
+    success: *mut u8,
+    borrowed_value: *mut *const c_char,
+    new_value_length: *mut size_t,
+) -> *mut c_char {
+    match result {
+        Some(MergeValue::Owned(result)) => {
+            let result = result.into_boxed_slice();
+            *new_value_length = result.len() as size_t;
+            *success = 1 as u8;
+            Box::into_raw(result) as *mut u8 as *mut c_char
+        }
+        Some(MergeValue::Borrowed(result)) => {
+            *borrowed_value = result.as_ptr() as *const c_char;
+            *new_value_length = result.len() as size_t;
+            *success = 1 as u8;
+            ptr::null_mut::<c_char>()
+        }
+        None => {
+            *new_value_length = 0;
+            *success = 0 as u8;
+            ptr::null_mut::<c_char>()
+        }
+    }
+}
+
+pub unsafe extern "C" fn delete_callback(
+    _raw_cb: *mut c_void,
+    value: *const c_char,
+    value_length: size_t,
+) {
+    if !value.is_null() {
+        let _: Box<[u8]> = Box::from_raw(slice::from_raw_parts_mut(
+            value as *mut u8,
+            value_length as usize,
+        ));
     }
 }
 
@@ -200,7 +273,7 @@ impl<'a> Iterator for &'a mut MergeOperands {
 #[cfg(test)]
 mod test {
 
-    use super::MergeOperands;
+    use super::{MergeOperands, MergeValue};
 
     fn test_provided_merge(
         _new_key: &[u8],
@@ -261,6 +334,83 @@ mod test {
         assert!(DB::destroy(&opts, path).is_ok());
     }
 
+    fn test_sum_full_merge(
+        _new_key: &[u8],
+        existing_val: Option<&[u8]>,
+        operands: &mut MergeOperands,
+    ) -> Option<Vec<u8>> {
+        let mut sum = existing_val.map_or(0, |v| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(v);
+            u64::from_le_bytes(bytes)
+        });
+        for op in operands {
+            sum += u64::from(op[0]);
+        }
+        Some(sum.to_le_bytes().to_vec())
+    }
+
+    #[test]
+    fn full_merge_only_test() {
+        use crate::{Options, DB};
+
+        let path = "_rust_rocksdb_full_merge_only_test";
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        // Operands and merged values differ in format, so the full merge must
+        // never be used to combine operands alone.
+        opts.set_merge_operator("sum operator", test_sum_full_merge, None);
+        {
+            let db = DB::open(&opts, path).unwrap();
+            for _ in 0..3 {
+                db.merge(b"k1", &[200]).unwrap();
+            }
+            db.flush().unwrap();
+            db.merge(b"k1", &[200]).unwrap();
+            assert_eq!(db.get(b"k1").unwrap().unwrap(), 800u64.to_le_bytes());
+        }
+        assert!(DB::destroy(&opts, path).is_ok());
+    }
+
+    fn test_max_merge<'a>(
+        _new_key: &[u8],
+        existing_val: Option<&'a [u8]>,
+        operands: &'a mut MergeOperands,
+    ) -> Option<MergeValue<'a>> {
+        existing_val
+            .into_iter()
+            .chain(operands)
+            .max()
+            .map(MergeValue::Borrowed)
+    }
+
+    #[test]
+    fn borrowing_merge_test() {
+        use crate::{Options, DB};
+
+        let path = "_rust_rocksdb_borrowing_merge_test";
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_borrowing_merge_operator("max", test_max_merge, Some(test_max_merge), true);
+        {
+            let db = DB::open(&opts, path).unwrap();
+            db.put(b"k1", b"b").unwrap();
+            db.merge(b"k1", b"a").unwrap();
+            db.merge(b"k1", b"d").unwrap();
+            db.merge(b"k2", b"c").unwrap();
+            db.flush().unwrap();
+            db.merge(b"k1", b"c").unwrap();
+            db.merge(b"k2", b"a").unwrap();
+            assert_eq!(db.get(b"k1").unwrap().unwrap(), b"d");
+            assert_eq!(db.get(b"k2").unwrap().unwrap(), b"c");
+
+            db.compact_range(None::<&[u8]>, None::<&[u8]>);
+            assert_eq!(db.get(b"k1").unwrap().unwrap(), b"d");
+            assert_eq!(db.get(b"k2").unwrap().unwrap(), b"c");
+        }
+        assert!(DB::destroy(&opts, path).is_ok());
+    }
+
     unsafe fn to_slice<T: Sized>(p: &T) -> &[u8] {
         ::std::slice::from_raw_parts((p as *const T) as *const u8, ::std::mem::size_of::<T>())
     }
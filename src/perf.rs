@@ -0,0 +1,217 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use libc::{c_int, c_uchar, c_void};
+use std::ffi::CStr;
+
+use crate::ffi;
+
+/// How much detail the perf context of a thread collects.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PerfLevel {
+    /// Collect nothing.
+    Disable = ffi::rocksdb_disable as isize,
+    /// Only collect counts.
+    EnableCount = ffi::rocksdb_enable_count as isize,
+    /// Collect counts and timings, except for mutex operations.
+    EnableTimeExceptForMutex = ffi::rocksdb_enable_time_except_for_mutex as isize,
+    /// Collect counts and all timings.
+    EnableTime = ffi::rocksdb_enable_time as isize,
+}
+
+/// Sets the perf level of the calling thread. Perf and IO stats are only
+/// collected for operations of threads with a level above `Disable`.
+pub fn set_perf_level(level: PerfLevel) {
+    unsafe {
+        ffi::rocksdb_set_perf_level(level as c_int);
+    }
+}
+
+/// A counter or timing of `PerfContext`. Timings are in nanoseconds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PerfMetric {
+    UserKeyComparisonCount = ffi::rocksdb_user_key_comparison_count as isize,
+    BlockCacheHitCount = ffi::rocksdb_block_cache_hit_count as isize,
+    BlockReadCount = ffi::rocksdb_block_read_count as isize,
+    BlockReadByte = ffi::rocksdb_block_read_byte as isize,
+    BlockReadTime = ffi::rocksdb_block_read_time as isize,
+    BlockChecksumTime = ffi::rocksdb_block_checksum_time as isize,
+    BlockDecompressTime = ffi::rocksdb_block_decompress_time as isize,
+    GetReadBytes = ffi::rocksdb_get_read_bytes as isize,
+    MultigetReadBytes = ffi::rocksdb_multiget_read_bytes as isize,
+    IterReadBytes = ffi::rocksdb_iter_read_bytes as isize,
+    InternalKeySkippedCount = ffi::rocksdb_internal_key_skipped_count as isize,
+    InternalDeleteSkippedCount = ffi::rocksdb_internal_delete_skipped_count as isize,
+    InternalRecentSkippedCount = ffi::rocksdb_internal_recent_skipped_count as isize,
+    InternalMergeCount = ffi::rocksdb_internal_merge_count as isize,
+    GetSnapshotTime = ffi::rocksdb_get_snapshot_time as isize,
+    GetFromMemtableTime = ffi::rocksdb_get_from_memtable_time as isize,
+    GetFromMemtableCount = ffi::rocksdb_get_from_memtable_count as isize,
+    GetPostProcessTime = ffi::rocksdb_get_post_process_time as isize,
+    GetFromOutputFilesTime = ffi::rocksdb_get_from_output_files_time as isize,
+    SeekOnMemtableTime = ffi::rocksdb_seek_on_memtable_time as isize,
+    SeekOnMemtableCount = ffi::rocksdb_seek_on_memtable_count as isize,
+    NextOnMemtableCount = ffi::rocksdb_next_on_memtable_count as isize,
+    PrevOnMemtableCount = ffi::rocksdb_prev_on_memtable_count as isize,
+    SeekChildSeekTime = ffi::rocksdb_seek_child_seek_time as isize,
+    SeekChildSeekCount = ffi::rocksdb_seek_child_seek_count as isize,
+    SeekMinHeapTime = ffi::rocksdb_seek_min_heap_time as isize,
+    SeekMaxHeapTime = ffi::rocksdb_seek_max_heap_time as isize,
+    SeekInternalSeekTime = ffi::rocksdb_seek_internal_seek_time as isize,
+    FindNextUserEntryTime = ffi::rocksdb_find_next_user_entry_time as isize,
+    WriteWalTime = ffi::rocksdb_write_wal_time as isize,
+    WriteMemtableTime = ffi::rocksdb_write_memtable_time as isize,
+    WriteDelayTime = ffi::rocksdb_write_delay_time as isize,
+    WritePreAndPostProcessTime = ffi::rocksdb_write_pre_and_post_process_time as isize,
+    DbMutexLockNanos = ffi::rocksdb_db_mutex_lock_nanos as isize,
+    DbConditionWaitNanos = ffi::rocksdb_db_condition_wait_nanos as isize,
+    MergeOperatorTimeNanos = ffi::rocksdb_merge_operator_time_nanos as isize,
+    ReadIndexBlockNanos = ffi::rocksdb_read_index_block_nanos as isize,
+    ReadFilterBlockNanos = ffi::rocksdb_read_filter_block_nanos as isize,
+    NewTableBlockIterNanos = ffi::rocksdb_new_table_block_iter_nanos as isize,
+    NewTableIteratorNanos = ffi::rocksdb_new_table_iterator_nanos as isize,
+    BlockSeekNanos = ffi::rocksdb_block_seek_nanos as isize,
+    FindTableNanos = ffi::rocksdb_find_table_nanos as isize,
+    BloomMemtableHitCount = ffi::rocksdb_bloom_memtable_hit_count as isize,
+    BloomMemtableMissCount = ffi::rocksdb_bloom_memtable_miss_count as isize,
+    BloomSstHitCount = ffi::rocksdb_bloom_sst_hit_count as isize,
+    BloomSstMissCount = ffi::rocksdb_bloom_sst_miss_count as isize,
+}
+
+/// The perf context of the calling thread, which counts the internal
+/// operations of the reads and writes made by the thread.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{DB, Options, PerfContext, PerfLevel, PerfMetric, set_perf_level};
+///
+/// let path = "_path_for_rocksdb_storage_perf";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     db.put(b"k1", b"v1").unwrap();
+///
+///     set_perf_level(PerfLevel::EnableTime);
+///     let mut ctx = PerfContext::default();
+///     ctx.reset();
+///     db.get(b"k1").unwrap();
+///     println!("{} ns in memtables", ctx.metric(PerfMetric::GetFromMemtableTime));
+///     set_perf_level(PerfLevel::Disable);
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct PerfContext {
+    inner: *mut ffi::rocksdb_perfcontext_t,
+}
+
+impl Default for PerfContext {
+    fn default() -> PerfContext {
+        let ctx = unsafe { ffi::rocksdb_perfcontext_create() };
+        if ctx.is_null() {
+            panic!("Could not create Perf Context");
+        }
+        PerfContext { inner: ctx }
+    }
+}
+
+impl Drop for PerfContext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_perfcontext_destroy(self.inner);
+        }
+    }
+}
+
+impl PerfContext {
+    /// Resets all counters and timings to zero.
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::rocksdb_perfcontext_reset(self.inner);
+        }
+    }
+
+    /// Returns a human readable report of all metrics.
+    pub fn report(&self, exclude_zero_counters: bool) -> String {
+        unsafe {
+            let ptr = ffi::rocksdb_perfcontext_report(self.inner, exclude_zero_counters as c_uchar);
+            let report = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+            libc::free(ptr as *mut c_void);
+            report
+        }
+    }
+
+    /// Returns the value of a metric.
+    pub fn metric(&self, metric: PerfMetric) -> u64 {
+        unsafe { ffi::rocksdb_perfcontext_metric(self.inner, metric as c_int) }
+    }
+}
+
+/// A snapshot of the IO stats context of the calling thread, which counts
+/// the file system operations of the thread. Timings are in nanoseconds.
+///
+/// Like `PerfContext`, it is only collected when the perf level of the
+/// thread is above `PerfLevel::Disable`.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct IOStatsContext {
+    pub bytes_written: u64,
+    pub bytes_read: u64,
+    pub open_nanos: u64,
+    pub allocate_nanos: u64,
+    pub write_nanos: u64,
+    pub read_nanos: u64,
+    pub range_sync_nanos: u64,
+    pub fsync_nanos: u64,
+    pub prepare_write_nanos: u64,
+    pub logger_nanos: u64,
+}
+
+impl IOStatsContext {
+    /// Returns the current IO stats of the calling thread.
+    pub fn get() -> IOStatsContext {
+        let mut stats = ffi::rocksdb_ext_iostats_t {
+            bytes_written: 0,
+            bytes_read: 0,
+            open_nanos: 0,
+            allocate_nanos: 0,
+            write_nanos: 0,
+            read_nanos: 0,
+            range_sync_nanos: 0,
+            fsync_nanos: 0,
+            prepare_write_nanos: 0,
+            logger_nanos: 0,
+        };
+        unsafe {
+            ffi::rocksdb_ext_iostats_context_get(&mut stats);
+        }
+        IOStatsContext {
+            bytes_written: stats.bytes_written,
+            bytes_read: stats.bytes_read,
+            open_nanos: stats.open_nanos,
+            allocate_nanos: stats.allocate_nanos,
+            write_nanos: stats.write_nanos,
+            read_nanos: stats.read_nanos,
+            range_sync_nanos: stats.range_sync_nanos,
+            fsync_nanos: stats.fsync_nanos,
+            prepare_write_nanos: stats.prepare_write_nanos,
+            logger_nanos: stats.logger_nanos,
+        }
+    }
+
+    /// Resets the IO stats of the calling thread to zero.
+    pub fn reset() {
+        unsafe {
+            ffi::rocksdb_ext_iostats_context_reset();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Names of commonly used properties, for use with `DB::property_value` and
+//! `DB::property_int_value` (and their `_cf` variants).
+//!
+//! The full list is documented in RocksDB's
+//! [db.h](https://github.com/facebook/rocksdb/blob/08809f5e6cd9cc4bc3958dd4d59457ae78c76660/include/rocksdb/db.h#L428-L634).
+
+/// Multi-line string of general statistics.
+pub const STATS: &str = "rocksdb.stats";
+/// Multi-line string with the number of files per level and the total size of each level.
+pub const LEVELSTATS: &str = "rocksdb.levelstats";
+/// Number of immutable memtables that have not yet been flushed.
+pub const NUM_IMMUTABLE_MEM_TABLE: &str = "rocksdb.num-immutable-mem-table";
+/// 1 if a memtable flush is pending, otherwise 0.
+pub const MEM_TABLE_FLUSH_PENDING: &str = "rocksdb.mem-table-flush-pending";
+/// 1 if at least one compaction is pending, otherwise 0.
+pub const COMPACTION_PENDING: &str = "rocksdb.compaction-pending";
+/// Approximate size of active and unflushed immutable memtables (bytes).
+pub const CUR_SIZE_ALL_MEM_TABLES: &str = "rocksdb.cur-size-all-mem-tables";
+/// Approximate size of active, unflushed immutable, and pinned immutable
+/// memtables (bytes).
+pub const SIZE_ALL_MEM_TABLES: &str = "rocksdb.size-all-mem-tables";
+/// Estimated number of total keys in the active and unflushed immutable
+/// memtables and storage.
+pub const ESTIMATE_NUM_KEYS: &str = "rocksdb.estimate-num-keys";
+/// Estimated memory used for reading SST tables, excluding memory used in
+/// block cache (e.g. filter and index blocks).
+pub const ESTIMATE_TABLE_READERS_MEM: &str = "rocksdb.estimate-table-readers-mem";
+/// Estimate of the amount of live data (bytes).
+pub const ESTIMATE_LIVE_DATA_SIZE: &str = "rocksdb.estimate-live-data-size";
+/// Total size (bytes) of all SST files.
+pub const TOTAL_SST_FILES_SIZE: &str = "rocksdb.total-sst-files-size";
+/// Estimated total number of bytes compaction needs to rewrite to get all
+/// levels down to under target size.
+pub const ESTIMATE_PENDING_COMPACTION_BYTES: &str = "rocksdb.estimate-pending-compaction-bytes";
+/// Number of currently running compactions.
+pub const NUM_RUNNING_COMPACTIONS: &str = "rocksdb.num-running-compactions";
+/// Number of currently running flushes.
+pub const NUM_RUNNING_FLUSHES: &str = "rocksdb.num-running-flushes";
+/// Current actual delayed write rate. 0 means no delay.
+pub const ACTUAL_DELAYED_WRITE_RATE: &str = "rocksdb.actual-delayed-write-rate";
+/// 1 if write has been stopped.
+pub const IS_WRITE_STOPPED: &str = "rocksdb.is-write-stopped";
+/// Block cache capacity (bytes).
+pub const BLOCK_CACHE_CAPACITY: &str = "rocksdb.block-cache-capacity";
+/// Memory size of the entries residing in the block cache (bytes).
+pub const BLOCK_CACHE_USAGE: &str = "rocksdb.block-cache-usage";
+/// Memory size of the entries pinned in the block cache (bytes).
+pub const BLOCK_CACHE_PINNED_USAGE: &str = "rocksdb.block-cache-pinned-usage";
@@ -34,6 +34,8 @@ pub struct SliceTransform {
 // opening a DB.
 
 impl SliceTransform {
+    /// Creates a transform from a Rust function. `in_domain_fn` tells whether a
+    /// key can be transformed at all; without it every key is in the domain.
     pub fn create(
         name: &str,
         transform_fn: TransformFn,
@@ -67,12 +69,15 @@ impl SliceTransform {
         SliceTransform { inner: st }
     }
 
+    /// Creates a transform that extracts the first `len` bytes of a key. Keys
+    /// shorter than `len` are not in its domain.
     pub fn create_fixed_prefix(len: size_t) -> SliceTransform {
         SliceTransform {
             inner: unsafe { ffi::rocksdb_slicetransform_create_fixed_prefix(len) },
         }
     }
 
+    /// Creates a transform that returns the whole key.
     pub fn create_noop() -> SliceTransform {
         SliceTransform {
             inner: unsafe { ffi::rocksdb_slicetransform_create_noop() },
@@ -14,6 +14,8 @@
 
 use crate::{ffi, ColumnFamily, DBIterator, DBRawIterator, Error, IteratorMode, ReadOptions, DB};
 
+use std::sync::Arc;
+
 /// A consistent view of the database at the point of creation.
 ///
 /// # Examples
@@ -45,6 +47,14 @@ impl<'a> Snapshot<'a> {
         }
     }
 
+    /// Returns the sequence number of the last write visible in this snapshot.
+    ///
+    /// Passing it to `DB::get_updates_since` returns the writes made after the snapshot
+    /// was taken.
+    pub fn sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_ext_snapshot_get_sequence_number(self.inner) }
+    }
+
     /// Creates an iterator over the data in this snapshot, using the default read options.
     pub fn iterator(&self, mode: IteratorMode) -> DBIterator<'a> {
         let readopts = ReadOptions::default();
@@ -157,3 +167,166 @@ impl<'a> Drop for Snapshot<'a> {
 /// immutable and can be safely shared between threads.
 unsafe impl<'a> Send for Snapshot<'a> {}
 unsafe impl<'a> Sync for Snapshot<'a> {}
+
+/// A consistent view of the database which owns a reference to it.
+///
+/// Unlike `Snapshot`, it does not borrow the `DB`, so it can be stored in a struct or moved
+/// to another thread. The snapshot keeps the database open until it is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{DB, OwnedSnapshot, Options};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let path = "_path_for_rocksdb_storage_owned_snapshot";
+/// {
+///     let db = Arc::new(DB::open_default(path).unwrap());
+///     db.put(b"k1", b"v1").unwrap();
+///     let snapshot = OwnedSnapshot::new(db.clone());
+///     db.put(b"k1", b"v2").unwrap();
+///     let value = thread::spawn(move || snapshot.get(b"k1").unwrap())
+///         .join()
+///         .unwrap();
+///     assert_eq!(value, Some(b"v1".to_vec()));
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct OwnedSnapshot {
+    db: Arc<DB>,
+    pub(crate) inner: *const ffi::rocksdb_snapshot_t,
+}
+
+impl OwnedSnapshot {
+    /// Creates a new `OwnedSnapshot` of the database `db`.
+    pub fn new(db: Arc<DB>) -> OwnedSnapshot {
+        let snapshot = unsafe { ffi::rocksdb_create_snapshot(db.inner) };
+        OwnedSnapshot {
+            db,
+            inner: snapshot,
+        }
+    }
+
+    /// Returns the database this snapshot was taken from.
+    pub fn db(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    /// See [`Snapshot::sequence_number`].
+    pub fn sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_ext_snapshot_get_sequence_number(self.inner) }
+    }
+
+    /// Creates an iterator over the data in this snapshot, using the default read options.
+    pub fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        let readopts = ReadOptions::default();
+        self.iterator_opt(mode, readopts)
+    }
+
+    /// Creates an iterator over the data in this snapshot under the given column family, using
+    /// the default read options.
+    pub fn iterator_cf(&self, cf_handle: &ColumnFamily, mode: IteratorMode) -> DBIterator {
+        let readopts = ReadOptions::default();
+        self.iterator_cf_opt(cf_handle, readopts, mode)
+    }
+
+    /// Creates an iterator over the data in this snapshot, using the given read options.
+    pub fn iterator_opt(&self, mode: IteratorMode, mut readopts: ReadOptions) -> DBIterator {
+        readopts.set_raw_snapshot(self.inner);
+        DBIterator::new(&self.db, readopts, mode)
+    }
+
+    /// Creates an iterator over the data in this snapshot under the given column family, using
+    /// the given read options.
+    pub fn iterator_cf_opt(
+        &self,
+        cf_handle: &ColumnFamily,
+        mut readopts: ReadOptions,
+        mode: IteratorMode,
+    ) -> DBIterator {
+        readopts.set_raw_snapshot(self.inner);
+        DBIterator::new_cf(&self.db, cf_handle, readopts, mode)
+    }
+
+    /// Creates a raw iterator over the data in this snapshot, using the default read options.
+    pub fn raw_iterator(&self) -> DBRawIterator {
+        let readopts = ReadOptions::default();
+        self.raw_iterator_opt(readopts)
+    }
+
+    /// Creates a raw iterator over the data in this snapshot under the given column family, using
+    /// the default read options.
+    pub fn raw_iterator_cf(&self, cf_handle: &ColumnFamily) -> DBRawIterator {
+        let readopts = ReadOptions::default();
+        self.raw_iterator_cf_opt(cf_handle, readopts)
+    }
+
+    /// Creates a raw iterator over the data in this snapshot, using the given read options.
+    pub fn raw_iterator_opt(&self, mut readopts: ReadOptions) -> DBRawIterator {
+        readopts.set_raw_snapshot(self.inner);
+        DBRawIterator::new(&self.db, readopts)
+    }
+
+    /// Creates a raw iterator over the data in this snapshot under the given column family, using
+    /// the given read options.
+    pub fn raw_iterator_cf_opt(
+        &self,
+        cf_handle: &ColumnFamily,
+        mut readopts: ReadOptions,
+    ) -> DBRawIterator {
+        readopts.set_raw_snapshot(self.inner);
+        DBRawIterator::new_cf(&self.db, cf_handle, readopts)
+    }
+
+    /// Returns the bytes associated with a key value with default read options.
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Result<Option<Vec<u8>>, Error> {
+        let readopts = ReadOptions::default();
+        self.get_opt(key, readopts)
+    }
+
+    /// Returns the bytes associated with a key value and given column family with default read
+    /// options.
+    pub fn get_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let readopts = ReadOptions::default();
+        self.get_cf_opt(cf, key.as_ref(), readopts)
+    }
+
+    /// Returns the bytes associated with a key value and given read options.
+    pub fn get_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        mut readopts: ReadOptions,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        readopts.set_raw_snapshot(self.inner);
+        self.db.get_opt(key.as_ref(), &readopts)
+    }
+
+    /// Returns the bytes associated with a key value, given column family and read options.
+    pub fn get_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        mut readopts: ReadOptions,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        readopts.set_raw_snapshot(self.inner);
+        self.db.get_cf_opt(cf, key.as_ref(), &readopts)
+    }
+}
+
+impl Drop for OwnedSnapshot {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_release_snapshot(self.db.inner, self.inner);
+        }
+    }
+}
+
+/// `Send` and `Sync` implementations for `OwnedSnapshot` are safe for the same reasons as for
+/// `Snapshot`, the database it refers to is kept alive by the `Arc`.
+unsafe impl Send for OwnedSnapshot {}
+unsafe impl Sync for OwnedSnapshot {}
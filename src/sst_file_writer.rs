@@ -67,12 +67,12 @@ impl<'a> SstFileWriter<'a> {
     }
 
     /// Prepare SstFileWriter to write into file located at "file_path".
-    pub fn open<P: AsRef<Path>>(&'a self, path: P) -> Result<(), Error> {
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
         let cpath = to_cpath(&path)?;
         self.open_raw(&cpath)
     }
 
-    fn open_raw(&'a self, cpath: &CString) -> Result<(), Error> {
+    fn open_raw(&self, cpath: &CString) -> Result<(), Error> {
         unsafe {
             ffi_try!(ffi::rocksdb_sstfilewriter_open(
                 self.inner,
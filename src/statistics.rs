@@ -0,0 +1,137 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A counter collected when statistics are enabled through
+/// `Options::enable_statistics`.
+///
+/// See [Options::get_ticker_count][get_ticker_count].
+///
+/// [get_ticker_count]: struct.Options.html#method.get_ticker_count
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Ticker {
+    BlockCacheMiss,
+    BlockCacheHit,
+    BlockCacheAdd,
+    BlockCacheIndexMiss,
+    BlockCacheIndexHit,
+    BlockCacheFilterMiss,
+    BlockCacheFilterHit,
+    BlockCacheDataMiss,
+    BlockCacheDataHit,
+    BloomFilterUseful,
+    MemtableHit,
+    MemtableMiss,
+    GetHitL0,
+    GetHitL1,
+    GetHitL2AndUp,
+    NumberKeysWritten,
+    NumberKeysRead,
+    NumberKeysUpdated,
+    BytesWritten,
+    BytesRead,
+    NumberDbSeek,
+    StallMicros,
+    CompactReadBytes,
+    CompactWriteBytes,
+    FlushWriteBytes,
+    WalFileSynced,
+    WalFileBytes,
+}
+
+impl Ticker {
+    /// The name RocksDB uses for this ticker, e.g. in `Options::get_statistics`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Ticker::BlockCacheMiss => "rocksdb.block.cache.miss",
+            Ticker::BlockCacheHit => "rocksdb.block.cache.hit",
+            Ticker::BlockCacheAdd => "rocksdb.block.cache.add",
+            Ticker::BlockCacheIndexMiss => "rocksdb.block.cache.index.miss",
+            Ticker::BlockCacheIndexHit => "rocksdb.block.cache.index.hit",
+            Ticker::BlockCacheFilterMiss => "rocksdb.block.cache.filter.miss",
+            Ticker::BlockCacheFilterHit => "rocksdb.block.cache.filter.hit",
+            Ticker::BlockCacheDataMiss => "rocksdb.block.cache.data.miss",
+            Ticker::BlockCacheDataHit => "rocksdb.block.cache.data.hit",
+            Ticker::BloomFilterUseful => "rocksdb.bloom.filter.useful",
+            Ticker::MemtableHit => "rocksdb.memtable.hit",
+            Ticker::MemtableMiss => "rocksdb.memtable.miss",
+            Ticker::GetHitL0 => "rocksdb.l0.hit",
+            Ticker::GetHitL1 => "rocksdb.l1.hit",
+            Ticker::GetHitL2AndUp => "rocksdb.l2andup.hit",
+            Ticker::NumberKeysWritten => "rocksdb.number.keys.written",
+            Ticker::NumberKeysRead => "rocksdb.number.keys.read",
+            Ticker::NumberKeysUpdated => "rocksdb.number.keys.updated",
+            Ticker::BytesWritten => "rocksdb.bytes.written",
+            Ticker::BytesRead => "rocksdb.bytes.read",
+            Ticker::NumberDbSeek => "rocksdb.number.db.seek",
+            Ticker::StallMicros => "rocksdb.stall.micros",
+            Ticker::CompactReadBytes => "rocksdb.compact.read.bytes",
+            Ticker::CompactWriteBytes => "rocksdb.compact.write.bytes",
+            Ticker::FlushWriteBytes => "rocksdb.flush.write.bytes",
+            Ticker::WalFileSynced => "rocksdb.wal.synced",
+            Ticker::WalFileBytes => "rocksdb.wal.bytes",
+        }
+    }
+}
+
+/// A distribution collected when statistics are enabled through
+/// `Options::enable_statistics`.
+///
+/// See [Options::get_histogram_data][get_histogram_data].
+///
+/// [get_histogram_data]: struct.Options.html#method.get_histogram_data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Histogram {
+    DbGet,
+    DbWrite,
+    DbMultiGet,
+    DbSeek,
+    CompactionTime,
+    FlushTime,
+    WalFileSyncMicros,
+    SstReadMicros,
+    BytesPerRead,
+    BytesPerWrite,
+}
+
+impl Histogram {
+    /// The name RocksDB uses for this histogram, e.g. in `Options::get_statistics`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Histogram::DbGet => "rocksdb.db.get.micros",
+            Histogram::DbWrite => "rocksdb.db.write.micros",
+            Histogram::DbMultiGet => "rocksdb.db.multiget.micros",
+            Histogram::DbSeek => "rocksdb.db.seek.micros",
+            Histogram::CompactionTime => "rocksdb.compaction.times.micros",
+            Histogram::FlushTime => "rocksdb.db.flush.micros",
+            Histogram::WalFileSyncMicros => "rocksdb.wal.file.sync.micros",
+            Histogram::SstReadMicros => "rocksdb.sst.read.micros",
+            Histogram::BytesPerRead => "rocksdb.bytes.per.read",
+            Histogram::BytesPerWrite => "rocksdb.bytes.per.write",
+        }
+    }
+}
+
+/// A snapshot of a `Histogram`. Durations are in microseconds.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct HistogramData {
+    pub median: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub average: f64,
+    pub std_dev: f64,
+    pub max: f64,
+    pub min: f64,
+    pub count: u64,
+    pub sum: u64,
+}
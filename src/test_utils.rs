@@ -0,0 +1,85 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for writing tests against a database, enabled by the `test-utils` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rocksdb::test_utils::TempDBPath;
+//! use rocksdb::DB;
+//!
+//! let path = TempDBPath::new("_my_test");
+//! {
+//!     let db = DB::open_default(&path).unwrap();
+//!     db.put(b"k1", b"v1").unwrap();
+//! }
+//! // The database is destroyed when `path` is dropped.
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::{Options, DB};
+
+/// A fresh database path in a temporary directory, which is destroyed with `DB::destroy` and
+/// removed when dropped.
+///
+/// Drop the databases opened at this path before the path itself, otherwise destroying it
+/// fails on the lock held by the database. Such a failure panics, unless the thread is
+/// already panicking, in which case it is ignored so that the original panic is reported.
+pub struct TempDBPath {
+    // Removes the directory after the database was destroyed.
+    _dir: tempfile::TempDir,
+    path: PathBuf,
+}
+
+impl TempDBPath {
+    /// Creates a path that doesn't exist yet, in a new temporary directory whose name starts
+    /// with `prefix` followed by random characters.
+    pub fn new(prefix: &str) -> TempDBPath {
+        let dir = tempfile::Builder::new()
+            .prefix(prefix)
+            .tempdir()
+            .expect("Failed to create temporary path for db.");
+        let path = dir.path().join("db");
+
+        TempDBPath { _dir: dir, path }
+    }
+
+    /// Returns the path of the database.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDBPath {
+    fn drop(&mut self) {
+        let opts = Options::default();
+        if let Err(e) = DB::destroy(&opts, &self.path) {
+            // Panicking again while unwinding would abort the process.
+            if !thread::panicking() {
+                panic!("Failed to destroy temporary DB: {}", e);
+            }
+        }
+    }
+}
+
+/// Only implemented for references, so that a `TempDBPath` isn't moved into e.g. `DB::open` and
+/// dropped before the database is closed.
+impl AsRef<Path> for &TempDBPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed keys and values on top of any store implementing `DynKvRead` or `DynKvStore`.
+//!
+//! A `TypedDb` encodes keys and values with a `Codec` on the way in and decodes them
+//! on the way out:
+//!
+//! ```
+//! use rocksdb::typed::{RawCodec, TypedDb};
+//! use rocksdb::{IteratorMode, Options, DB};
+//!
+//! let path = "_path_for_rocksdb_storage_typed";
+//! {
+//!     let db = DB::open_default(path).unwrap();
+//!     let users: TypedDb<_, u64, String, RawCodec> = TypedDb::new(&db);
+//!     users.put(&2, &"bob".to_owned()).unwrap();
+//!     users.put(&1, &"alice".to_owned()).unwrap();
+//!
+//!     assert_eq!(users.get(&1).unwrap(), Some("alice".to_owned()));
+//!     let ids: Vec<u64> = users
+//!         .iterator(IteratorMode::Start)
+//!         .map(|entry| entry.unwrap().0)
+//!         .collect();
+//!     assert_eq!(ids, vec![1, 2]);
+//! }
+//! let _ = DB::destroy(&Options::default(), path);
+//! ```
+//!
+//! With the `serde-bincode` feature, `BincodeCodec` stores any serde type.
+
+use crate::{DBIterator, Direction, DynKvRead, DynKvStore, Error, IteratorMode};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Converts values of type `T` to and from bytes.
+///
+/// Keys are ordered by their encoding, so codecs used for keys should preserve the
+/// order of the values if the store is iterated.
+pub trait Codec<T> {
+    fn encode(value: &T) -> Result<Vec<u8>, Error>;
+    fn decode(bytes: &[u8]) -> Result<T, Error>;
+}
+
+/// Stores byte vectors as is, strings as UTF-8 and integers in big-endian order, so
+/// that integer keys are iterated in numeric order.
+pub struct RawCodec;
+
+impl Codec<Vec<u8>> for RawCodec {
+    fn encode(value: &Vec<u8>) -> Result<Vec<u8>, Error> {
+        Ok(value.clone())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl Codec<String> for RawCodec {
+    fn encode(value: &String) -> Result<Vec<u8>, Error> {
+        Ok(value.as_bytes().to_vec())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<String, Error> {
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| Error::new(format!("Invalid UTF-8 value: {}", e)))
+    }
+}
+
+macro_rules! impl_raw_codec_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Codec<$t> for RawCodec {
+                fn encode(value: &$t) -> Result<Vec<u8>, Error> {
+                    Ok(value.to_be_bytes().to_vec())
+                }
+
+                fn decode(bytes: &[u8]) -> Result<$t, Error> {
+                    let bytes = bytes.try_into().map_err(|_| {
+                        Error::new(format!(
+                            "Invalid {} value: {} bytes",
+                            stringify!($t),
+                            bytes.len()
+                        ))
+                    })?;
+                    Ok(<$t>::from_be_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_raw_codec_for_int!(u16, u32, u64, u128);
+
+/// Stores any serde type with bincode.
+///
+/// Bincode writes integers in little-endian order, so the encoded keys are not
+/// iterated in numeric order.
+#[cfg(feature = "serde-bincode")]
+pub struct BincodeCodec;
+
+#[cfg(feature = "serde-bincode")]
+impl<T> Codec<T> for BincodeCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(value: &T) -> Result<Vec<u8>, Error> {
+        bincode::serialize(value).map_err(|e| Error::new(format!("Failed to encode: {}", e)))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, Error> {
+        bincode::deserialize(bytes).map_err(|e| Error::new(format!("Failed to decode: {}", e)))
+    }
+}
+
+/// A store whose keys and values are of type `K` and `V`, encoded with the codec `C`.
+///
+/// `S` is a reference or pointer to the underlying store, such as `&DB`, `Arc<DB>`,
+/// `&Snapshot` or `Box<dyn DynKvStore>`. Reads are available if it implements
+/// `DynKvRead`, writes if it implements `DynKvStore`.
+pub struct TypedDb<S, K, V, C> {
+    store: S,
+    marker: PhantomData<fn() -> (K, V, C)>,
+}
+
+impl<S, K, V, C> TypedDb<S, K, V, C>
+where
+    S: Deref,
+    C: Codec<K> + Codec<V>,
+{
+    pub fn new(store: S) -> TypedDb<S, K, V, C> {
+        TypedDb {
+            store,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying store.
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+}
+
+impl<S, K, V, C> TypedDb<S, K, V, C>
+where
+    S: Deref,
+    S::Target: DynKvRead,
+    C: Codec<K> + Codec<V>,
+{
+    /// Returns the value of `key`, if any.
+    pub fn get(&self, key: &K) -> Result<Option<V>, Error> {
+        let key = <C as Codec<K>>::encode(key)?;
+        match self.store.get(&key)? {
+            Some(value) => Ok(Some(<C as Codec<V>>::decode(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates an iterator over the decoded entries of the store.
+    pub fn iterator(&self, mode: IteratorMode) -> TypedIterator<K, V, C> {
+        TypedIterator {
+            inner: self.store.iterator(mode),
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates an iterator starting at `key`, or at the next key in the given direction
+    /// if it doesn't exist.
+    pub fn iterator_from(
+        &self,
+        key: &K,
+        direction: Direction,
+    ) -> Result<TypedIterator<K, V, C>, Error> {
+        let key = <C as Codec<K>>::encode(key)?;
+        Ok(TypedIterator {
+            inner: self.store.iterator(IteratorMode::From(&key, direction)),
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<S, K, V, C> TypedDb<S, K, V, C>
+where
+    S: Deref,
+    S::Target: DynKvStore,
+    C: Codec<K> + Codec<V>,
+{
+    /// Sets the value of `key`.
+    pub fn put(&self, key: &K, value: &V) -> Result<(), Error> {
+        let key = <C as Codec<K>>::encode(key)?;
+        let value = <C as Codec<V>>::encode(value)?;
+        self.store.put(&key, &value)
+    }
+
+    /// Removes `key`.
+    pub fn delete(&self, key: &K) -> Result<(), Error> {
+        let key = <C as Codec<K>>::encode(key)?;
+        self.store.delete(&key)
+    }
+}
+
+/// An iterator over the decoded entries of a `TypedDb`. Entries that fail to decode
+/// are returned as errors.
+pub struct TypedIterator<'a, K, V, C> {
+    inner: DBIterator<'a>,
+    marker: PhantomData<fn() -> (K, V, C)>,
+}
+
+impl<'a, K, V, C> TypedIterator<'a, K, V, C> {
+    /// See [`status`](crate::DBRawIterator::status)
+    pub fn status(&self) -> Result<(), Error> {
+        self.inner.status()
+    }
+}
+
+impl<'a, K, V, C> Iterator for TypedIterator<'a, K, V, C>
+where
+    C: Codec<K> + Codec<V>,
+{
+    type Item = Result<(K, V), Error>;
+
+    fn next(&mut self) -> Option<Result<(K, V), Error>> {
+        let (key, value) = self.inner.next()?;
+        Some(
+            <C as Codec<K>>::decode(&key)
+                .and_then(|key| Ok((key, <C as Codec<V>>::decode(&value)?))),
+        )
+    }
+}
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{ffi, ColumnFamily};
+use crate::{ffi, ColumnFamily, Error};
 use libc::{c_char, c_void, size_t};
+use std::ptr;
 use std::slice;
 
 /// An atomic batch of write operations.
@@ -77,7 +78,200 @@ unsafe extern "C" fn writebatch_delete_callback(state: *mut c_void, k: *const c_
     leaked_cb.delete(key.to_vec().into_boxed_slice());
 }
 
+/// Receives the puts, deletes and merges of a write batch together with the id
+/// of the column family each of them targets.
+///
+/// Column family ids can be mapped back to names with `DB::cf_name`.
+pub trait WriteBatchIteratorCf {
+    /// Called with a key and value that were `put` into the batch.
+    fn put_cf(&mut self, cf_id: u32, key: Box<[u8]>, value: Box<[u8]>);
+    /// Called with a key that was `delete`d from the batch.
+    fn delete_cf(&mut self, cf_id: u32, key: Box<[u8]>);
+    /// Called with a key that was `single_delete`d from the batch. Calls `delete_cf` by
+    /// default.
+    fn single_delete_cf(&mut self, cf_id: u32, key: Box<[u8]>) {
+        self.delete_cf(cf_id, key);
+    }
+    /// Called with a key and value that were `merge`d into the batch.
+    fn merge_cf(&mut self, cf_id: u32, key: Box<[u8]>, value: Box<[u8]>);
+    /// Called with the bounds of a `delete_range`. Ignored by default.
+    fn delete_range_cf(&mut self, _cf_id: u32, _from: Box<[u8]>, _to: Box<[u8]>) {}
+}
+
+unsafe extern "C" fn writebatch_put_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+    v: *const c_char,
+    vlen: usize,
+) {
+    let cb = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen as usize);
+    let value = slice::from_raw_parts(v as *const u8, vlen as usize);
+    cb.put_cf(
+        cf_id,
+        key.to_vec().into_boxed_slice(),
+        value.to_vec().into_boxed_slice(),
+    );
+}
+
+unsafe extern "C" fn writebatch_delete_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+) {
+    let cb = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen as usize);
+    cb.delete_cf(cf_id, key.to_vec().into_boxed_slice());
+}
+
+unsafe extern "C" fn writebatch_single_delete_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+) {
+    let cb = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen as usize);
+    cb.single_delete_cf(cf_id, key.to_vec().into_boxed_slice());
+}
+
+unsafe extern "C" fn writebatch_merge_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+    v: *const c_char,
+    vlen: usize,
+) {
+    let cb = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen as usize);
+    let value = slice::from_raw_parts(v as *const u8, vlen as usize);
+    cb.merge_cf(
+        cf_id,
+        key.to_vec().into_boxed_slice(),
+        value.to_vec().into_boxed_slice(),
+    );
+}
+
+unsafe extern "C" fn writebatch_delete_range_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    from: *const c_char,
+    from_len: usize,
+    to: *const c_char,
+    to_len: usize,
+) {
+    let cb = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let from = slice::from_raw_parts(from as *const u8, from_len as usize);
+    let to = slice::from_raw_parts(to as *const u8, to_len as usize);
+    cb.delete_range_cf(
+        cf_id,
+        from.to_vec().into_boxed_slice(),
+        to.to_vec().into_boxed_slice(),
+    );
+}
+
+/// An operation of a write batch, as returned by `WriteBatch::ops`.
+///
+/// `cf_id` is the id of the column family the operation targets, which can be mapped
+/// back to its name with `DB::cf_name`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchOp {
+    Put {
+        cf_id: u32,
+        key: Box<[u8]>,
+        value: Box<[u8]>,
+    },
+    Delete {
+        cf_id: u32,
+        key: Box<[u8]>,
+    },
+    SingleDelete {
+        cf_id: u32,
+        key: Box<[u8]>,
+    },
+    Merge {
+        cf_id: u32,
+        key: Box<[u8]>,
+        value: Box<[u8]>,
+    },
+    /// A `delete_range` of the keys in `[from, to)`.
+    DeleteRange {
+        cf_id: u32,
+        from: Box<[u8]>,
+        to: Box<[u8]>,
+    },
+}
+
+impl WriteBatchIteratorCf for Vec<BatchOp> {
+    fn put_cf(&mut self, cf_id: u32, key: Box<[u8]>, value: Box<[u8]>) {
+        self.push(BatchOp::Put { cf_id, key, value });
+    }
+
+    fn delete_cf(&mut self, cf_id: u32, key: Box<[u8]>) {
+        self.push(BatchOp::Delete { cf_id, key });
+    }
+
+    fn single_delete_cf(&mut self, cf_id: u32, key: Box<[u8]>) {
+        self.push(BatchOp::SingleDelete { cf_id, key });
+    }
+
+    fn merge_cf(&mut self, cf_id: u32, key: Box<[u8]>, value: Box<[u8]>) {
+        self.push(BatchOp::Merge { cf_id, key, value });
+    }
+
+    fn delete_range_cf(&mut self, cf_id: u32, from: Box<[u8]>, to: Box<[u8]>) {
+        self.push(BatchOp::DeleteRange { cf_id, from, to });
+    }
+}
+
+/// Size of the header of a serialized write batch: an 8 bytes sequence number
+/// followed by a 4 bytes count.
+const WRITE_BATCH_HEADER_SIZE: usize = 12;
+
 impl WriteBatch {
+    /// Creates a write batch from its serialized representation, as returned by
+    /// `WriteBatch::data`, e.g. to apply a batch read from the WAL of another database.
+    pub fn from_data(data: &[u8]) -> Result<WriteBatch, Error> {
+        if data.len() < WRITE_BATCH_HEADER_SIZE {
+            return Err(Error::new(format!(
+                "Write batch data is too short: {} bytes",
+                data.len()
+            )));
+        }
+        Ok(WriteBatch {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_create_from(data.as_ptr() as *const c_char, data.len())
+            },
+        })
+    }
+
+    /// Returns the serialized representation of the batch.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let mut size: size_t = 0;
+            let data = ffi::rocksdb_writebatch_data(self.inner, &mut size);
+            slice::from_raw_parts(data as *const u8, size)
+        }
+    }
+
+    /// Returns the sequence number stored in the header of the batch.
+    ///
+    /// For batches returned by `DB::get_updates_since` this is the sequence number of
+    /// the first operation in the source database, and it is kept by `data` and `from_data`.
+    /// Writing the batch to another database assigns new sequence numbers, so replicas
+    /// should track this value to resume reading from the source. It is `0` for batches
+    /// that were built by the application.
+    pub fn sequence_number(&self) -> u64 {
+        let mut header = [0; 8];
+        header.copy_from_slice(&self.data()[..8]);
+        u64::from_le_bytes(header)
+    }
+
+    /// Returns the number of operations in the batch.
     pub fn len(&self) -> usize {
         unsafe { ffi::rocksdb_writebatch_count(self.inner) as usize }
     }
@@ -114,6 +308,53 @@ impl WriteBatch {
         }
     }
 
+    /// Returns the operations of this write batch, in the order they were added.
+    ///
+    /// Fails if the batch is corrupted, e.g. when it was created with `from_data` from
+    /// truncated data.
+    ///
+    /// ```
+    /// use rocksdb::{BatchOp, WriteBatch};
+    ///
+    /// let mut batch = WriteBatch::default();
+    /// batch.put(b"k1", b"v1");
+    /// batch.delete(b"k2");
+    ///
+    /// for op in batch.ops().unwrap() {
+    ///     match op {
+    ///         BatchOp::Put { key, value, .. } => println!("put {:?} {:?}", key, value),
+    ///         BatchOp::Delete { key, .. } => println!("delete {:?}", key),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn ops(&self) -> Result<impl Iterator<Item = BatchOp>, Error> {
+        let mut ops = Vec::with_capacity(self.len());
+        self.iterate_cf(&mut ops)?;
+        Ok(ops.into_iter())
+    }
+
+    /// Iterate the put, delete, merge and range deletion operations within this
+    /// write batch, reporting the id of the column family each operation applies to.
+    ///
+    /// Fails if the batch is corrupted, after reporting the operations before the
+    /// corruption.
+    pub fn iterate_cf(&self, callbacks: &mut dyn WriteBatchIteratorCf) -> Result<(), Error> {
+        let mut state = callbacks;
+        unsafe {
+            ffi_try!(ffi::rocksdb_ext_writebatch_iterate_cf(
+                self.inner,
+                &mut state as *mut &mut dyn WriteBatchIteratorCf as *mut c_void,
+                Some(writebatch_put_cf_callback),
+                Some(writebatch_delete_cf_callback),
+                Some(writebatch_single_delete_cf_callback),
+                Some(writebatch_merge_cf_callback),
+                Some(writebatch_delete_range_cf_callback),
+            ));
+        }
+        Ok(())
+    }
+
     /// Insert a value into the database under the given key.
     pub fn put<K, V>(&mut self, key: K, value: V)
     where
@@ -219,6 +460,34 @@ impl WriteBatch {
         }
     }
 
+    /// Remove the database entry for a key that was written at most once,
+    /// see `DB::single_delete_opt`.
+    pub fn single_delete<K: AsRef<[u8]>>(&mut self, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_ext_writebatch_singledelete_cf(
+                self.inner,
+                ptr::null_mut(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
+    pub fn single_delete_cf<K: AsRef<[u8]>>(&mut self, cf: &ColumnFamily, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_ext_writebatch_singledelete_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
     /// Remove database entries from start key to end key.
     ///
     /// Removes the database entries in the range ["begin_key", "end_key"), i.e.,
@@ -279,3 +548,5 @@ impl Drop for WriteBatch {
         unsafe { ffi::rocksdb_writebatch_destroy(self.inner) }
     }
 }
+
+unsafe impl Send for WriteBatch {}
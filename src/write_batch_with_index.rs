@@ -0,0 +1,375 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    ffi, ffi_util::raw_data, ColumnFamily, DBRawIterator, Error, Options, ReadOptions, DB,
+};
+use libc::{self, c_char, c_uchar, c_void, size_t};
+
+/// A write batch that keeps a searchable index of its writes, so they can be
+/// read back before the batch is written to the database.
+///
+/// ```
+/// use rocksdb::{DB, Options, ReadOptions, WriteBatchWithIndex};
+///
+/// let path = "_path_for_rocksdb_storage_wbwi";
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     db.put(b"k1", b"v1").unwrap();
+///
+///     let mut batch = WriteBatchWithIndex::default();
+///     batch.put(b"k2", b"v2");
+///     batch.delete(b"k1");
+///
+///     // Pending writes are visible through the batch only.
+///     assert!(batch.get_from_batch_and_db(&db, b"k1").unwrap().is_none());
+///     assert_eq!(batch.get_from_batch_and_db(&db, b"k2").unwrap().unwrap(), b"v2");
+///     assert!(db.get(b"k2").unwrap().is_none());
+///
+///     db.write_wbwi(&batch).unwrap();
+///     assert_eq!(db.get(b"k2").unwrap().unwrap(), b"v2");
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct WriteBatchWithIndex {
+    pub(crate) inner: *mut ffi::rocksdb_writebatch_wi_t,
+    overwrite_key: bool,
+}
+
+impl WriteBatchWithIndex {
+    /// Creates a batch with `reserved_bytes` preallocated. If `overwrite_key`
+    /// is true, a later write to a key replaces an earlier one in the index,
+    /// which also lets reads of merged keys be resolved from the batch.
+    ///
+    /// `raw_iterator_with_base` and `raw_iterator_with_base_cf` require
+    /// `overwrite_key`, which `WriteBatchWithIndex::default` sets.
+    pub fn new(reserved_bytes: usize, overwrite_key: bool) -> WriteBatchWithIndex {
+        WriteBatchWithIndex {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_wi_create(
+                    reserved_bytes as size_t,
+                    overwrite_key as c_uchar,
+                )
+            },
+            overwrite_key,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { ffi::rocksdb_writebatch_wi_count(self.inner) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert a value into the batch under the given key.
+    pub fn put<K, V>(&mut self, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+        }
+    }
+
+    pub fn put_cf<K, V>(&mut self, cf: &ColumnFamily, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+        }
+    }
+
+    pub fn merge<K, V>(&mut self, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_merge(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+        }
+    }
+
+    pub fn merge_cf<K, V>(&mut self, cf: &ColumnFamily, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_merge_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+        }
+    }
+
+    /// Remove the database entry for key.
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
+    pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf: &ColumnFamily, key: K) {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+        }
+    }
+
+    /// Clear all updates buffered in this batch.
+    pub fn clear(&mut self) {
+        unsafe {
+            ffi::rocksdb_writebatch_wi_clear(self.inner);
+        }
+    }
+
+    /// Records the state of the batch for future calls to `rollback_to_save_point`.
+    pub fn set_save_point(&mut self) {
+        unsafe {
+            ffi::rocksdb_writebatch_wi_set_save_point(self.inner);
+        }
+    }
+
+    /// Removes all writes made since the most recent call to `set_save_point`.
+    /// Fails if there is no save point.
+    pub fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_writebatch_wi_rollback_to_save_point(
+                self.inner
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reads a key from the batch only. `opts` must be the options the
+    /// database is opened with, to resolve merges.
+    pub fn get_from_batch<K: AsRef<[u8]>>(
+        &self,
+        opts: &Options,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch(
+                self.inner,
+                opts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            ));
+            Ok(take_value(val, val_len))
+        }
+    }
+
+    /// Reads a key of a column family from the batch only.
+    pub fn get_from_batch_cf<K: AsRef<[u8]>>(
+        &self,
+        opts: &Options,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_cf(
+                self.inner,
+                opts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            ));
+            Ok(take_value(val, val_len))
+        }
+    }
+
+    /// Reads a key as if the batch was already written to `db`.
+    pub fn get_from_batch_and_db<K: AsRef<[u8]>>(
+        &self,
+        db: &DB,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.get_from_batch_and_db_opt(db, key, &ReadOptions::default())
+    }
+
+    pub fn get_from_batch_and_db_opt<K: AsRef<[u8]>>(
+        &self,
+        db: &DB,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db(
+                self.inner,
+                db.inner,
+                readopts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            ));
+            Ok(take_value(val, val_len))
+        }
+    }
+
+    /// Reads a key of a column family as if the batch was already written to `db`.
+    pub fn get_from_batch_and_db_cf<K: AsRef<[u8]>>(
+        &self,
+        db: &DB,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.get_from_batch_and_db_cf_opt(db, cf, key, &ReadOptions::default())
+    }
+
+    pub fn get_from_batch_and_db_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        db: &DB,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db_cf(
+                self.inner,
+                db.inner,
+                readopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            ));
+            Ok(take_value(val, val_len))
+        }
+    }
+
+    /// Returns a raw iterator over the database with the writes of this batch
+    /// applied on top.
+    ///
+    /// # Panics
+    ///
+    /// If the batch was created without `overwrite_key`, which RocksDB requires.
+    pub fn raw_iterator_with_base<'a>(
+        &'a self,
+        db: &'a DB,
+        readopts: ReadOptions,
+    ) -> DBRawIterator<'a> {
+        self.assert_overwrite_key();
+        DBRawIterator::new_with_batch(db, self, None, readopts)
+    }
+
+    /// Returns a raw iterator over a column family with the writes of this
+    /// batch applied on top.
+    ///
+    /// # Panics
+    ///
+    /// If the batch was created without `overwrite_key`, which RocksDB requires.
+    pub fn raw_iterator_with_base_cf<'a>(
+        &'a self,
+        db: &'a DB,
+        cf: &ColumnFamily,
+        readopts: ReadOptions,
+    ) -> DBRawIterator<'a> {
+        self.assert_overwrite_key();
+        DBRawIterator::new_with_batch(db, self, Some(cf), readopts)
+    }
+
+    fn assert_overwrite_key(&self) {
+        // RocksDB returns no iterator at all otherwise.
+        assert!(
+            self.overwrite_key,
+            "iterating a WriteBatchWithIndex with a base requires overwrite_key"
+        );
+    }
+}
+
+/// Copies a value returned by RocksDB and frees the original.
+unsafe fn take_value(val: *mut c_char, val_len: size_t) -> Option<Vec<u8>> {
+    let value = raw_data(val, val_len as usize);
+    if !val.is_null() {
+        libc::free(val as *mut c_void);
+    }
+    value
+}
+
+/// Creates an empty batch with `overwrite_key` set.
+impl Default for WriteBatchWithIndex {
+    fn default() -> WriteBatchWithIndex {
+        WriteBatchWithIndex::new(0, true)
+    }
+}
+
+impl Drop for WriteBatchWithIndex {
+    fn drop(&mut self) {
+        unsafe { ffi::rocksdb_writebatch_wi_destroy(self.inner) }
+    }
+}
+
+unsafe impl Send for WriteBatchWithIndex {}
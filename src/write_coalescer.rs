@@ -0,0 +1,234 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::{Error, WriteBatch, WriteOptions, DB};
+
+/// Groups the writes of many threads into a single `WriteBatch` written with one
+/// synced write, so that they share the cost of syncing the WAL.
+///
+/// Each write blocks until the batch containing it has been written. A batch is
+/// written once it holds `max_batch_ops` writes, or `max_delay` after its first
+/// write, by a background thread that writes the pending writes and stops when the
+/// coalescer is dropped.
+///
+/// Results are per batch, not per write: the writes of a batch are applied
+/// atomically, so if writing the batch fails, every write in it fails with the same
+/// error, including writes from other threads that were fine on their own. None of
+/// the writes of a failed batch are applied, so they can all be retried.
+///
+/// # Examples
+///
+/// ```
+/// use rocksdb::{Options, WriteCoalescer, DB};
+/// use std::sync::Arc;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let path = "_path_for_rocksdb_storage_write_coalescer";
+/// {
+///     let db = Arc::new(DB::open_default(path).unwrap());
+///     let coalescer = Arc::new(WriteCoalescer::new(
+///         db.clone(),
+///         128,
+///         Duration::from_millis(1),
+///     ));
+///     let writers: Vec<_> = (0..4u8)
+///         .map(|i| {
+///             let coalescer = coalescer.clone();
+///             thread::spawn(move || coalescer.put(vec![i], b"value").unwrap())
+///         })
+///         .collect();
+///     for writer in writers {
+///         writer.join().unwrap();
+///     }
+///     assert_eq!(db.get([3u8]).unwrap().unwrap(), b"value");
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct WriteCoalescer {
+    db: Arc<DB>,
+    shared: Arc<Shared>,
+    flusher: Option<JoinHandle<()>>,
+}
+
+enum Op {
+    Put(Vec<u8>, Vec<u8>),
+    Delete(Vec<u8>),
+}
+
+#[derive(Default)]
+struct Completion {
+    result: Mutex<Option<Result<(), Error>>>,
+    cond: Condvar,
+}
+
+impl Completion {
+    fn complete(&self, result: Result<(), Error>) {
+        *self.result.lock().unwrap() = Some(result);
+        self.cond.notify_all();
+    }
+
+    fn wait(&self) -> Result<(), Error> {
+        let mut result = self.result.lock().unwrap();
+        loop {
+            match result.take() {
+                Some(result) => return result,
+                None => result = self.cond.wait(result).unwrap(),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Pending {
+    ops: Vec<Op>,
+    completions: Vec<Arc<Completion>>,
+    first_at: Option<Instant>,
+    closed: bool,
+}
+
+struct Shared {
+    pending: Mutex<Pending>,
+    cond: Condvar,
+    max_batch_ops: usize,
+    max_delay: Duration,
+}
+
+impl WriteCoalescer {
+    /// Creates a coalescer writing to `db` and starts its background thread.
+    ///
+    /// `max_batch_ops` is raised to 1 if it is 0.
+    pub fn new(db: Arc<DB>, max_batch_ops: usize, max_delay: Duration) -> WriteCoalescer {
+        let shared = Arc::new(Shared {
+            pending: Mutex::new(Pending::default()),
+            cond: Condvar::new(),
+            max_batch_ops: max_batch_ops.max(1),
+            max_delay,
+        });
+        let flusher = {
+            let db = db.clone();
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("rocksdb:coalescer".to_owned())
+                .spawn(move || run_flusher(&db, &shared))
+                .expect("failed to spawn the write coalescer thread")
+        };
+        WriteCoalescer {
+            db,
+            shared,
+            flusher: Some(flusher),
+        }
+    }
+
+    /// Returns the database the writes go to.
+    pub fn db(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    /// Sets the value of `key` and waits until the batch containing the write has been
+    /// written.
+    pub fn put<K, V>(&self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.submit(Op::Put(key.as_ref().to_vec(), value.as_ref().to_vec()))
+    }
+
+    /// Removes `key` and waits until the batch containing the write has been written.
+    pub fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), Error> {
+        self.submit(Op::Delete(key.as_ref().to_vec()))
+    }
+
+    fn submit(&self, op: Op) -> Result<(), Error> {
+        let completion = Arc::new(Completion::default());
+        {
+            let mut pending = self.shared.pending.lock().unwrap();
+            if pending.closed {
+                return Err(Error::new("Write coalescer is closed".to_owned()));
+            }
+            pending.ops.push(op);
+            pending.completions.push(completion.clone());
+            if pending.first_at.is_none() {
+                pending.first_at = Some(Instant::now());
+                self.shared.cond.notify_one();
+            } else if pending.ops.len() >= self.shared.max_batch_ops {
+                self.shared.cond.notify_one();
+            }
+        }
+        completion.wait()
+    }
+}
+
+impl Drop for WriteCoalescer {
+    /// Writes the pending writes and stops the background thread.
+    fn drop(&mut self) {
+        self.shared.pending.lock().unwrap().closed = true;
+        self.shared.cond.notify_one();
+        if let Some(flusher) = self.flusher.take() {
+            let _ = flusher.join();
+        }
+    }
+}
+
+fn run_flusher(db: &DB, shared: &Shared) {
+    let mut writeopts = WriteOptions::default();
+    writeopts.set_sync(true);
+
+    let mut pending = shared.pending.lock().unwrap();
+    loop {
+        let first_at = match pending.first_at {
+            Some(first_at) => first_at,
+            None if pending.closed => return,
+            None => {
+                pending = shared.cond.wait(pending).unwrap();
+                continue;
+            }
+        };
+        let elapsed = first_at.elapsed();
+        if !pending.closed && pending.ops.len() < shared.max_batch_ops && elapsed < shared.max_delay
+        {
+            pending = shared
+                .cond
+                .wait_timeout(pending, shared.max_delay - elapsed)
+                .unwrap()
+                .0;
+            continue;
+        }
+
+        let ops = mem::replace(&mut pending.ops, Vec::new());
+        let completions = mem::replace(&mut pending.completions, Vec::new());
+        pending.first_at = None;
+        drop(pending);
+
+        let mut batch = WriteBatch::default();
+        for op in ops {
+            match op {
+                Op::Put(key, value) => batch.put(key, value),
+                Op::Delete(key) => batch.delete(key),
+            }
+        }
+        let result = db.write_opt(batch, &writeopts);
+        for completion in completions {
+            completion.complete(result.clone());
+        }
+
+        pending = shared.pending.lock().unwrap();
+    }
+}
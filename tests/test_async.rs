@@ -0,0 +1,58 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "async")]
+
+mod util;
+
+use rocksdb::{AsyncDB, WriteBatch, DB};
+use util::DBPath;
+
+#[tokio::test]
+async fn test_async_db() {
+    let path = DBPath::new("_rust_rocksdb_test_async_db");
+    let db = AsyncDB::new(DB::open_default(&path).unwrap());
+
+    db.put(b"k1".to_vec(), b"v1".to_vec()).await.unwrap();
+    let mut batch = WriteBatch::default();
+    batch.put(b"k2", b"v2");
+    batch.put(b"k3", b"v3");
+    db.write(batch).await.unwrap();
+    db.delete(b"k3".to_vec()).await.unwrap();
+
+    assert_eq!(db.get(b"k1".to_vec()).await.unwrap().unwrap(), b"v1");
+    let values: Vec<_> = db
+        .multi_get(vec![b"k1".to_vec(), b"k2".to_vec(), b"k3".to_vec()])
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        values,
+        vec![Some(b"v1".to_vec()), Some(b"v2".to_vec()), None]
+    );
+
+    // Clones share the database and can be moved to other tasks.
+    let clone = db.clone();
+    tokio::spawn(async move { clone.put(b"k4".to_vec(), b"v4".to_vec()).await })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(db.get(b"k4".to_vec()).await.unwrap().unwrap(), b"v4");
+
+    let count = db
+        .run(|db| db.iterator(rocksdb::IteratorMode::Start).count())
+        .await;
+    assert_eq!(count, 3);
+}
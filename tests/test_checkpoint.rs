@@ -97,3 +97,9 @@ pub fn test_multi_checkpoints() {
     assert_eq!(*cp.get(b"k5").unwrap().unwrap(), *b"v5");
     assert_eq!(*cp.get(b"k6").unwrap().unwrap(), *b"v6");
 }
+
+#[test]
+fn test_checkpoint_outlive_db() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/checkpoint_outlive_db.rs");
+}
@@ -14,7 +14,12 @@
 
 mod util;
 
-use rocksdb::{ColumnFamilyDescriptor, MergeOperands, Options, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use rocksdb::{
+    BoundColumnFamily, ColumnFamilyDescriptor, MergeOperands, Options, WriteBatch,
+    WriteBatchIteratorCf, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use std::sync::Arc;
+use std::thread;
 use util::DBPath;
 
 #[test]
@@ -26,7 +31,7 @@ fn test_column_family() {
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.set_merge_operator("test operator", test_provided_merge, None);
-        let mut db = DB::open(&opts, &n).unwrap();
+        let db = DB::open(&opts, &n).unwrap();
         let opts = Options::default();
         match db.create_cf("cf1", &opts) {
             Ok(()) => println!("cf1 created successfully"),
@@ -79,7 +84,7 @@ fn test_column_family() {
     {}
     // should b able to drop a cf
     {
-        let mut db = DB::open_cf(&Options::default(), &n, &["cf1"]).unwrap();
+        let db = DB::open_cf(&Options::default(), &n, &["cf1"]).unwrap();
         match db.drop_cf("cf1") {
             Ok(_) => println!("cf1 successfully dropped."),
             Err(e) => panic!("failed to drop column family: {}", e),
@@ -96,7 +101,7 @@ fn test_can_open_db_with_results_of_list_cf() {
     {
         let mut opts = Options::default();
         opts.create_if_missing(true);
-        let mut db = DB::open(&opts, &n).unwrap();
+        let db = DB::open(&opts, &n).unwrap();
         let opts = Options::default();
 
         assert!(db.create_cf("cf1", &opts).is_ok());
@@ -235,6 +240,31 @@ fn test_column_family_with_options() {
     }
 }
 
+#[test]
+fn test_optimized_column_family_descriptors() {
+    let n = DBPath::new("_rust_rocksdb_optimized_cf_descriptors_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let cfs = vec![
+            ColumnFamilyDescriptor::new_for_point_lookup("lookup", 8),
+            ColumnFamilyDescriptor::new_for_level_style_compaction("level", 64 << 20),
+            ColumnFamilyDescriptor::new_for_universal_style_compaction("universal", 64 << 20),
+        ];
+        assert_eq!(cfs[0].name(), "lookup");
+        let db = DB::open_cf_descriptors(&opts, &n, cfs).unwrap();
+
+        for name in &["lookup", "level", "universal"] {
+            let cf = db.cf_handle(name).unwrap();
+            db.put_cf(cf, b"k1", b"v1").unwrap();
+            db.flush_cf(cf).unwrap();
+            assert_eq!(db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+        }
+    }
+}
+
 #[test]
 fn test_create_duplicate_column_family() {
     let n = DBPath::new("_rust_rocksdb_create_duplicate_column_family");
@@ -243,7 +273,7 @@ fn test_create_duplicate_column_family() {
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
 
-        let mut db = match DB::open_cf(&opts, &n, &["cf1"]) {
+        let db = match DB::open_cf(&opts, &n, &["cf1"]) {
             Ok(d) => d,
             Err(e) => panic!("failed to create new column family: {}", e),
         };
@@ -251,3 +281,193 @@ fn test_create_duplicate_column_family() {
         assert!(db.create_cf("cf1", &opts).is_err());
     }
 }
+
+#[derive(Default)]
+struct CfOperations {
+    ops: Vec<(u32, &'static str, Box<[u8]>)>,
+}
+
+impl WriteBatchIteratorCf for CfOperations {
+    fn put_cf(&mut self, cf_id: u32, key: Box<[u8]>, _value: Box<[u8]>) {
+        self.ops.push((cf_id, "put", key));
+    }
+    fn delete_cf(&mut self, cf_id: u32, key: Box<[u8]>) {
+        self.ops.push((cf_id, "delete", key));
+    }
+    fn merge_cf(&mut self, cf_id: u32, key: Box<[u8]>, _value: Box<[u8]>) {
+        self.ops.push((cf_id, "merge", key));
+    }
+}
+
+#[test]
+fn test_write_batch_iterate_cf() {
+    let n = DBPath::new("_rust_rocksdb_write_batch_iterate_cf");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let db = DB::open_cf(&opts, &n, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert_ne!(cf1.id(), 0);
+        assert_eq!(db.cf_name(0).as_deref(), Some(DEFAULT_COLUMN_FAMILY_NAME));
+        assert_eq!(db.cf_name(cf1.id()).as_deref(), Some("cf1"));
+        assert_eq!(db.cf_name(cf1.id() + 1), None);
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k1", b"v1");
+        batch.put_cf(cf1, b"k2", b"v2");
+        batch.delete_cf(cf1, b"k3");
+        batch.merge_cf(cf1, b"k4", b"v4");
+
+        let mut operations = CfOperations::default();
+        batch.iterate_cf(&mut operations).unwrap();
+        let names: Vec<_> = operations
+            .ops
+            .iter()
+            .map(|(id, _, _)| db.cf_name(*id).unwrap())
+            .collect();
+        let ops: Vec<_> = operations
+            .ops
+            .iter()
+            .zip(&names)
+            .map(|((_, op, key), name)| (name.as_str(), *op, &key[..]))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                ("default", "put", &b"k1"[..]),
+                ("cf1", "put", &b"k2"[..]),
+                ("cf1", "delete", &b"k3"[..]),
+                ("cf1", "merge", &b"k4"[..]),
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_create_and_drop_cf_concurrently() {
+    let n = DBPath::new("_rust_rocksdb_cftest_concurrent");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let db = Arc::new(DB::open(&opts, &n).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    let name = format!("cf{}", i);
+                    db.create_cf(&name, &Options::default()).unwrap();
+                    let cf = BoundColumnFamily::new(db.clone(), &name).unwrap();
+                    db.put_cf(&cf, b"k", name.as_bytes()).unwrap();
+                    assert_eq!(db.get_cf(&cf, b"k").unwrap().unwrap(), name.as_bytes());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let cf = BoundColumnFamily::new(db.clone(), "cf0").unwrap();
+        db.drop_cf("cf0").unwrap();
+        assert!(BoundColumnFamily::new(db.clone(), "cf0").is_none());
+        assert!(db.drop_cf("cf0").is_err());
+        // The handle of a dropped column family can still be used safely.
+        let _ = db.get_cf(&cf, b"k");
+
+        for i in 1..4 {
+            assert!(BoundColumnFamily::new(db.clone(), &format!("cf{}", i)).is_some());
+        }
+    }
+}
+
+#[test]
+fn test_bound_column_family() {
+    let n = DBPath::new("_rust_rocksdb_cftest_bound");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = Arc::new(DB::open_cf(&opts, &n, &["cf1"]).unwrap());
+        assert!(BoundColumnFamily::new(db.clone(), "cf2").is_none());
+
+        let cf = BoundColumnFamily::new(db.clone(), "cf1").unwrap();
+        drop(db);
+
+        let handle = thread::spawn(move || {
+            cf.db().put_cf(&cf, b"k1", b"v1").unwrap();
+            assert_eq!(cf.db().get_cf(&cf, b"k1").unwrap().unwrap(), b"v1");
+            cf.db().drop_cf("cf1").unwrap();
+            assert!(cf.db().put_cf(&cf, b"k2", b"v2").is_err());
+        });
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_release_dropped_column_families() {
+    let n = DBPath::new("_rust_rocksdb_cftest_release_dropped");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let db = Arc::new(DB::open(&opts, &n).unwrap());
+
+        // Only held by bound handles, freed when the last one is dropped.
+        db.create_cf("bound", &opts).unwrap();
+        let cf = BoundColumnFamily::new(db.clone(), "bound").unwrap();
+        let cf2 = cf.clone();
+        db.drop_cf("bound").unwrap();
+        assert!(db.cf_handle("bound").is_none());
+        drop(cf);
+        assert!(db.put_cf(&cf2, b"k", b"v").is_err());
+        drop(cf2);
+
+        // Lent out by `cf_handle`, kept until released.
+        db.create_cf("lent", &opts).unwrap();
+        db.put_cf(db.cf_handle("lent").unwrap(), b"k", b"v")
+            .unwrap();
+        db.drop_cf("lent").unwrap();
+        assert!(db.drop_cf("lent").is_err());
+
+        let mut db = Arc::try_unwrap(db).ok().unwrap();
+        db.release_dropped_cfs();
+
+        db.create_cf("lent", &opts).unwrap();
+        let cf = db.cf_handle("lent").unwrap();
+        assert!(db.get_cf(cf, b"k").unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_bound_column_family_churn() {
+    let n = DBPath::new("_rust_rocksdb_cftest_bound_churn");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let db = Arc::new(DB::open(&opts, &n).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    let name = format!("churn{}", i);
+                    for round in 0..10u8 {
+                        db.create_cf(&name, &Options::default()).unwrap();
+                        let cf = BoundColumnFamily::new(db.clone(), &name).unwrap();
+                        // Each incarnation of the column family starts out empty.
+                        assert!(db.get_cf(&cf, b"k").unwrap().is_none());
+                        db.put_cf(&cf, b"k", [round]).unwrap();
+                        assert_eq!(db.get_cf(&cf, b"k").unwrap().unwrap(), [round]);
+                        db.drop_cf(&name).unwrap();
+                        assert!(db.put_cf(&cf, b"k", b"v").is_err());
+                    }
+                    assert!(BoundColumnFamily::new(db.clone(), &name).is_none());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -14,7 +14,9 @@
 
 mod util;
 
-use rocksdb::{CompactionDecision, Options, DB};
+use rocksdb::{compaction_filter::TtlCompactionFilter, CompactionDecision, Options, DB};
+use std::thread;
+use std::time::Duration;
 use util::DBPath;
 
 #[cfg(test)]
@@ -45,3 +47,28 @@ fn compaction_filter_test() {
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
     }
 }
+
+#[test]
+fn ttl_compaction_filter_test() {
+    let path = DBPath::new("_rust_rocksdb_ttl_filter_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_ttl_compaction_filter(TtlCompactionFilter::new(Duration::from_secs(1)));
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", TtlCompactionFilter::encode_value(b"v1"))
+            .unwrap();
+        // values without a write time are never expired
+        db.put(b"k2", b"").unwrap();
+        let value = db.get(b"k1").unwrap().unwrap();
+        assert_eq!(TtlCompactionFilter::decode_value(&value).unwrap(), b"v1");
+
+        thread::sleep(Duration::from_secs(3));
+        db.put(b"k3", TtlCompactionFilter::encode_value(b"v3"))
+            .unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get(b"k2").unwrap().is_some());
+        assert!(db.get(b"k3").unwrap().is_some());
+    }
+}
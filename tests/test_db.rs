@@ -14,7 +14,13 @@
 
 mod util;
 
-use rocksdb::{Error, IteratorMode, Options, Snapshot, WriteBatch, DB};
+use rocksdb::{
+    set_perf_level, BatchOp, BottommostLevelCompaction, ColumnFamilyDescriptor, CompactOptions,
+    DynKvRead, DynKvStore, Env, Error, ErrorKind, Histogram, IOStatsContext, IteratorMode, Options,
+    OwnedSnapshot, PerfContext, PerfLevel, PerfMetric, Range, Snapshot, Ticker, WalReadOptions,
+    WriteBatch, WriteOptions, DB,
+};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{mem, thread};
@@ -73,6 +79,27 @@ fn errors_do_stuff() {
     }
 }
 
+#[test]
+fn error_kind_test() {
+    let path = DBPath::new("_rust_rocksdb_error_kind");
+    let db = DB::open_default(&path).unwrap();
+    assert_eq!(
+        DB::destroy(&Options::default(), &path).unwrap_err().kind(),
+        ErrorKind::IOError
+    );
+    assert_eq!(
+        db.set_options(&[("INVALID_NAME", "INVALID_VALUE")])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidArgument
+    );
+    // errors detected before calling RocksDB have no kind
+    assert_eq!(
+        db.drop_cf("missing").unwrap_err().kind(),
+        ErrorKind::Unknown
+    );
+}
+
 #[test]
 fn writebatch_works() {
     let path = DBPath::new("_rust_rocksdb_writebacktest");
@@ -187,6 +214,47 @@ fn sync_snapshot_test() {
     assert!(handler_2.join().unwrap());
 }
 
+#[test]
+fn owned_snapshot_test() {
+    let path = DBPath::new("_rust_rocksdb_owned_snapshottest");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+
+    assert!(db.put(b"k1", b"v1").is_ok());
+    let snapshot = OwnedSnapshot::new(db.clone());
+    assert!(db.put(b"k1", b"v2").is_ok());
+    assert!(db.put(b"k2", b"v2").is_ok());
+
+    let handler = thread::spawn(move || {
+        assert_eq!(snapshot.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(snapshot.get(b"k2").unwrap().is_none());
+        snapshot.iterator(IteratorMode::Start).count()
+    });
+    assert_eq!(handler.join().unwrap(), 1);
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v2");
+}
+
+#[test]
+fn dyn_kv_store_test() {
+    let path = DBPath::new("_rust_rocksdb_dyn_kv_store_test");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+
+    let store: &dyn DynKvStore = &*db;
+    store.put(b"k1", b"v1").unwrap();
+    store.put(b"k3", b"v3").unwrap();
+    assert_eq!(store.get(b"k1").unwrap().unwrap(), b"v1");
+    store.delete(b"k3").unwrap();
+    assert!(store.get(b"k3").unwrap().is_none());
+
+    let snapshot = OwnedSnapshot::new(db.clone());
+    db.put(b"k2", b"v2").unwrap();
+    let readers: Vec<&dyn DynKvRead> = vec![&*db, &snapshot];
+    let counts: Vec<_> = readers
+        .iter()
+        .map(|r| r.iterator(IteratorMode::Start).count())
+        .collect();
+    assert_eq!(counts, vec![2, 1]);
+}
+
 #[test]
 fn set_option_test() {
     let path = DBPath::new("_rust_rocksdb_set_optionstest");
@@ -224,6 +292,37 @@ fn set_option_test() {
     }
 }
 
+#[test]
+fn set_options_cf_test() {
+    let path = DBPath::new("_rust_rocksdb_set_options_cf_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+
+        db.set_options_cf(cf, &[("disable_auto_compactions", "true")])
+            .unwrap();
+        db.set_options_cf(cf, &[("write_buffer_size", "8388608")])
+            .unwrap();
+        assert!(db
+            .set_options_cf(cf, &[("INVALID_NAME", "INVALID_VALUE")])
+            .is_err());
+        // database wide options are rejected for column families and vice versa
+        assert!(db
+            .set_options_cf(cf, &[("max_background_jobs", "4")])
+            .is_err());
+        db.set_db_options(&[("max_background_jobs", "4")]).unwrap();
+        assert!(db
+            .set_db_options(&[("disable_auto_compactions", "true")])
+            .is_err());
+        assert!(db
+            .set_db_options(&[("max_background_jobs\0", "4")])
+            .is_err());
+    }
+}
+
 #[test]
 fn test_sequence_number() {
     let path = DBPath::new("_rust_rocksdb_test_sequence_number");
@@ -326,6 +425,89 @@ fn test_get_updates_since_nothing() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+fn test_get_updates_since_opt() {
+    let path = DBPath::new("_rust_rocksdb_test_get_updates_since_opt");
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"key1", b"value1").unwrap();
+    db.put(b"key2", b"value2").unwrap();
+    let mut opts = WalReadOptions::default();
+    opts.set_verify_checksums(true);
+    let mut iter = db.get_updates_since_opt(1, &opts).unwrap();
+    let (seq, batch) = iter.next().unwrap();
+    assert_eq!(seq, 2);
+    assert_eq!(batch.len(), 1);
+    assert!(iter.next().is_none());
+    // reaching the end of the WAL is not an error
+    assert!(!iter.valid());
+    assert!(iter.status().is_ok());
+}
+
+#[test]
+fn test_replicate_with_sequence_numbers() {
+    let primary_path = DBPath::new("_rust_rocksdb_test_replicate_primary");
+    let replica_path = DBPath::new("_rust_rocksdb_test_replicate_replica");
+    let primary = DB::open_default(&primary_path).unwrap();
+    let replica = DB::open_default(&replica_path).unwrap();
+    // the replica has writes of its own, so its sequence numbers diverge
+    replica.put(b"local", b"value").unwrap();
+
+    primary.put(b"key1", b"value1").unwrap();
+    let start = primary.latest_sequence_number();
+    let mut batch = WriteBatch::default();
+    batch.put(b"key2", b"value2");
+    batch.put(b"key3", b"value3");
+    primary.write(batch).unwrap();
+
+    let mut applied = 0;
+    for (seq, batch) in primary.get_updates_since(start).unwrap() {
+        let shipped = WriteBatch::from_data(batch.data()).unwrap();
+        assert_eq!(shipped.sequence_number(), seq);
+        applied = shipped.sequence_number() + shipped.len() as u64 - 1;
+        replica.write(shipped).unwrap();
+    }
+    assert_eq!(applied, primary.latest_sequence_number());
+    assert_eq!(replica.get(b"key3").unwrap().unwrap(), b"value3");
+    assert_eq!(WriteBatch::default().sequence_number(), 0);
+}
+
+#[test]
+fn test_snapshot_sequence_number() {
+    let path = DBPath::new("_rust_rocksdb_test_snapshot_sequence_number");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+    db.put(b"key1", b"value1").unwrap();
+    db.put(b"key2", b"value2").unwrap();
+
+    let snapshot = db.snapshot();
+    assert_eq!(snapshot.sequence_number(), db.latest_sequence_number());
+    let owned = OwnedSnapshot::new(db.clone());
+    assert_eq!(owned.sequence_number(), snapshot.sequence_number());
+
+    db.put(b"key3", b"value3").unwrap();
+    db.delete(b"key1").unwrap();
+
+    // resume reading the WAL where the snapshot ends
+    let ops: Vec<_> = db
+        .get_updates_since(snapshot.sequence_number())
+        .unwrap()
+        .flat_map(|(_, batch)| batch.ops().unwrap().collect::<Vec<_>>())
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            BatchOp::Put {
+                cf_id: 0,
+                key: b"key3".to_vec().into_boxed_slice(),
+                value: b"value3".to_vec().into_boxed_slice(),
+            },
+            BatchOp::Delete {
+                cf_id: 0,
+                key: b"key1".to_vec().into_boxed_slice(),
+            },
+        ]
+    );
+}
+
 #[test]
 fn test_get_updates_since_out_of_range() {
     let path = DBPath::new("_rust_rocksdb_test_get_updates_since_out_of_range");
@@ -359,6 +541,40 @@ fn test_open_as_secondary() {
     assert_eq!(get_byte_slice(&result), b"value2");
 }
 
+#[test]
+fn test_open_cf_descriptors_as_secondary() {
+    let primary_path = DBPath::new("_rust_rocksdb_test_open_cf_descriptors_as_secondary_primary");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &primary_path, &["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put_cf(cf1, b"key1", b"value1").unwrap();
+
+    let mut opts = Options::default();
+    opts.set_max_open_files(-1);
+
+    let secondary_path =
+        DBPath::new("_rust_rocksdb_test_open_cf_descriptors_as_secondary_secondary");
+    // The two paths don't have to be of the same type.
+    let secondary_dir: &Path = (&secondary_path).as_ref();
+    let cfs = vec![ColumnFamilyDescriptor::new("cf1", Options::default())];
+    let secondary =
+        DB::open_cf_descriptors_as_secondary(&opts, &primary_path, secondary_dir, cfs).unwrap();
+    let secondary_cf1 = secondary.cf_handle("cf1").unwrap();
+
+    let result = secondary.get_cf(secondary_cf1, b"key1").unwrap().unwrap();
+    assert_eq!(get_byte_slice(&result), b"value1");
+
+    db.put_cf(cf1, b"key1", b"value2").unwrap();
+    assert!(secondary.try_catch_up_with_primary().is_ok());
+
+    let result = secondary.get_cf(secondary_cf1, b"key1").unwrap().unwrap();
+    assert_eq!(get_byte_slice(&result), b"value2");
+    assert!(secondary.put_cf(secondary_cf1, b"key2", b"value").is_err());
+}
+
 #[test]
 fn test_open_with_ttl() {
     let path = DBPath::new("_rust_rocksdb_test_open_with_ttl");
@@ -374,3 +590,482 @@ fn test_open_with_ttl() {
     db.compact_range(None::<&[u8]>, None::<&[u8]>);
     assert!(db.get(b"key1").unwrap().is_none());
 }
+
+#[test]
+fn test_open_cf_descriptors_with_ttl() {
+    let path = DBPath::new("_rust_rocksdb_test_open_cf_descriptors_with_ttl");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let cfs = vec![
+        ColumnFamilyDescriptor::new("short", Options::default()),
+        ColumnFamilyDescriptor::new_with_ttl("long", Options::default(), Duration::from_secs(3600)),
+    ];
+    let db = DB::open_cf_descriptors_with_ttl(&opts, &path, cfs, Duration::from_secs(1)).unwrap();
+    let short = db.cf_handle("short").unwrap();
+    let long = db.cf_handle("long").unwrap();
+    db.put_cf(short, b"key1", b"value1").unwrap();
+    db.put_cf(long, b"key1", b"value1").unwrap();
+
+    thread::sleep(Duration::from_secs(2));
+    db.compact_range_cf(short, None::<&[u8]>, None::<&[u8]>);
+    db.compact_range_cf(long, None::<&[u8]>, None::<&[u8]>);
+    assert!(db.get_cf(short, b"key1").unwrap().is_none());
+    assert_eq!(db.get_cf(long, b"key1").unwrap().unwrap(), b"value1");
+}
+
+#[test]
+fn env_test() {
+    let path = DBPath::new("_rust_rocksdb_env_test");
+
+    let mut env = Env::mem_env().unwrap();
+    env.set_background_threads(2);
+    env.set_high_priority_background_threads(1);
+    env.lower_thread_pool_io_priority();
+
+    let db = {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_env(&env);
+        // the database keeps the env alive
+        drop(env);
+        DB::open(&opts, &path).unwrap()
+    };
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    // the files only exist in memory
+    assert!(!path.as_ref().join("CURRENT").exists());
+}
+
+#[test]
+fn perf_context_test() {
+    let path = DBPath::new("_rust_rocksdb_perf_context_test");
+    let db = DB::open_default(&path).unwrap();
+    for i in 0..100 {
+        db.put(format!("key{}", i), b"value").unwrap();
+    }
+    db.flush().unwrap();
+
+    set_perf_level(PerfLevel::EnableTime);
+    let mut ctx = PerfContext::default();
+    ctx.reset();
+    IOStatsContext::reset();
+
+    assert_eq!(db.get(b"key1").unwrap().unwrap(), b"value");
+    assert!(ctx.metric(PerfMetric::BlockReadCount) > 0);
+    assert!(ctx.metric(PerfMetric::GetFromOutputFilesTime) > 0);
+    assert!(ctx.report(true).contains("block_read_count"));
+    assert!(IOStatsContext::get().bytes_read > 0);
+
+    set_perf_level(PerfLevel::Disable);
+    ctx.reset();
+    db.get(b"key2").unwrap();
+    assert_eq!(ctx.metric(PerfMetric::BlockReadCount), 0);
+}
+
+#[test]
+fn key_may_exist_test() {
+    let path = DBPath::new("_rust_rocksdb_key_may_exist_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put_cf(cf1, b"k2", b"v2").unwrap();
+
+    // Values still in the memtable are found without IO.
+    assert_eq!(db.key_may_exist(b"k1"), (true, Some(b"v1".to_vec())));
+    assert_eq!(
+        db.key_may_exist_cf(cf1, b"k2"),
+        (true, Some(b"v2".to_vec()))
+    );
+    assert!(!db.key_may_exist(b"k2").0);
+    assert!(!db.key_may_exist_cf(cf1, b"k1").0);
+}
+
+#[test]
+fn single_delete_test() {
+    let path = DBPath::new("_rust_rocksdb_single_delete_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.put_cf(cf1, b"k3", b"v3").unwrap();
+    db.put_cf(cf1, b"k4", b"v4").unwrap();
+
+    db.single_delete(b"k1").unwrap();
+    db.single_delete_cf(cf1, b"k3").unwrap();
+    assert!(db.get(b"k1").unwrap().is_none());
+    assert!(db.get_cf(cf1, b"k3").unwrap().is_none());
+
+    let mut batch = WriteBatch::default();
+    batch.single_delete(b"k2");
+    batch.single_delete_cf(cf1, b"k4");
+    db.write(batch).unwrap();
+    assert!(db.get(b"k2").unwrap().is_none());
+    assert!(db.get_cf(cf1, b"k4").unwrap().is_none());
+}
+
+#[test]
+fn load_latest_options_test() {
+    let path = DBPath::new("_rust_rocksdb_load_latest_options_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_max_open_files(100);
+        let db = DB::open_cf(&opts, &path, &["cf1", "cf2"]).unwrap();
+        db.put_cf(db.cf_handle("cf2").unwrap(), b"k1", b"v1")
+            .unwrap();
+    }
+
+    let (opts, cfs) = Options::load_latest(&path, false).unwrap();
+    let mut names: Vec<_> = cfs.iter().map(|cf| cf.name().to_owned()).collect();
+    names.sort();
+    assert_eq!(names, vec!["cf1", "cf2", "default"]);
+
+    let db = DB::open_cf_descriptors(&opts, &path, cfs).unwrap();
+    let cf2 = db.cf_handle("cf2").unwrap();
+    assert_eq!(db.get_cf(cf2, b"k1").unwrap().unwrap(), b"v1");
+    drop(db);
+
+    DB::repair(&opts, &path).unwrap();
+}
+
+#[test]
+fn multi_get() {
+    let path = DBPath::new("_rust_rocksdb_multi_get");
+
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let values = db
+            .multi_get(&[b"k0", b"k1", b"k2"])
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            values,
+            vec![None, Some(b"v1".to_vec()), Some(b"v2".to_vec())]
+        );
+    }
+}
+
+#[test]
+fn multi_get_cf() {
+    let path = DBPath::new("_rust_rocksdb_multi_get_cf");
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf0", "cf1", "cf2"]).unwrap();
+
+        let cf0 = db.cf_handle("cf0").unwrap();
+
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        let cf2 = db.cf_handle("cf2").unwrap();
+        db.put_cf(cf2, b"k2", b"v2").unwrap();
+
+        let values = db
+            .multi_get_cf(vec![(cf0, b"k0"), (cf1, b"k1"), (cf2, b"k2")])
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            values,
+            vec![None, Some(b"v1".to_vec()), Some(b"v2".to_vec())]
+        );
+    }
+}
+
+#[test]
+fn delete_range_test() {
+    let path = DBPath::new("_rust_rocksdb_delete_range_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for key in &[b"k1", b"k2", b"k3", b"k4"] {
+            db.put(key, b"value").unwrap();
+        }
+
+        db.delete_range(b"k2", b"k4").unwrap();
+
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(db.get(b"k2").unwrap().is_none());
+        assert!(db.get(b"k3").unwrap().is_none());
+        assert!(db.get(b"k4").unwrap().is_some());
+    }
+}
+
+#[test]
+fn statistics_test() {
+    let path = DBPath::new("_rust_rocksdb_statistics_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysWritten), 1);
+        assert_eq!(opts.get_ticker_count(Ticker::MemtableHit), 1);
+        assert_eq!(opts.get_ticker_count(Ticker::MemtableMiss), 1);
+
+        let get = opts.get_histogram_data(Histogram::DbGet).unwrap();
+        assert_eq!(get.count, 2);
+    }
+}
+
+#[test]
+fn approximate_sizes_test() {
+    let path = DBPath::new("_rust_rocksdb_approximate_sizes_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{:04}", i), vec![b'x'; 100]).unwrap();
+        }
+
+        let all = Range::new(b"key", b"kez");
+        let none = Range::new(b"a", b"b");
+
+        let (count, size) = db.approximate_memtable_stats(all);
+        assert!(count > 0);
+        assert!(size > 0);
+        assert_eq!(db.approximate_memtable_stats(none), (0, 0));
+
+        db.flush().unwrap();
+
+        let sizes = db.approximate_sizes(&[all, none]);
+        assert_eq!(sizes.len(), 2);
+        assert!(sizes[0] > 0);
+        assert_eq!(sizes[1], 0);
+    }
+}
+
+#[test]
+fn compact_range_opt_test() {
+    let path = DBPath::new("_rust_rocksdb_compact_range_opt_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+        db.flush_cf(cf1).unwrap();
+        let files_at_level = |level: u32| {
+            db.property_int_value_cf(cf1, &format!("rocksdb.num-files-at-level{}", level))
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(files_at_level(0), 1);
+
+        let mut compact_opts = CompactOptions::default();
+        compact_opts.set_exclusive_manual_compaction(true);
+        compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+        compact_opts.set_change_level(true);
+        compact_opts.set_target_level(2);
+        db.compact_range_cf_opt(cf1, None::<&[u8]>, None::<&[u8]>, &compact_opts);
+
+        assert_eq!(files_at_level(0), 0);
+        assert_eq!(files_at_level(2), 1);
+        assert_eq!(db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn flush_cfs_test() {
+    let path = DBPath::new("_rust_rocksdb_flush_cfs_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_atomic_flush(true);
+        let db = DB::open_cf(&opts, &path, &["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf2, b"k2", b"v2").unwrap();
+        db.flush_cfs(&[cf1, cf2]).unwrap();
+
+        for cf in &[cf1, cf2] {
+            let entries = db
+                .property_int_value_cf(cf, "rocksdb.num-entries-active-mem-table")
+                .unwrap();
+            assert_eq!(entries, Some(0));
+        }
+        assert_eq!(db.get_cf(cf2, b"k2").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn atomic_flush_without_wal_test() {
+    let path = DBPath::new("_rust_rocksdb_atomic_flush_without_wal_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_atomic_flush(true);
+    {
+        let db = DB::open_cf(&opts, &path, &["data", "index"]).unwrap();
+        let data = db.cf_handle("data").unwrap();
+        let index = db.cf_handle("index").unwrap();
+
+        let mut write_opts = WriteOptions::default();
+        write_opts.disable_wal(true);
+        let mut batch = WriteBatch::default();
+        batch.put_cf(data, b"k1", b"v1");
+        batch.put_cf(index, b"v1", b"k1");
+        db.write_opt(batch, &write_opts).unwrap();
+        db.flush_cfs(&[data, index]).unwrap();
+    }
+    {
+        // without a WAL, only the flushed data survives reopening
+        let db = DB::open_cf(&opts, &path, &["data", "index"]).unwrap();
+        let data = db.cf_handle("data").unwrap();
+        let index = db.cf_handle("index").unwrap();
+        assert_eq!(db.get_cf(data, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get_cf(index, b"v1").unwrap().unwrap(), b"k1");
+    }
+}
+
+#[test]
+fn wal_management_test() {
+    let path = DBPath::new("_rust_rocksdb_wal_management_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_manual_wal_flush(true);
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.flush_wal(true).is_ok());
+        assert!(db.sync_wal().is_ok());
+
+        assert!(db.disable_file_deletions().is_ok());
+        assert!(db.disable_file_deletions().is_ok());
+        assert!(db.enable_file_deletions(false).is_ok());
+        assert!(db.enable_file_deletions(true).is_ok());
+    }
+}
+
+#[test]
+fn pause_background_work_test() {
+    let path = DBPath::new("_rust_rocksdb_pause_background_work_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        db.pause_background_work().unwrap();
+        db.pause_background_work().unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.continue_background_work().unwrap();
+        db.continue_background_work().unwrap();
+        // there is nothing left to continue
+        assert!(db.continue_background_work().is_err());
+
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn cancel_all_background_work_test() {
+    let path = DBPath::new("_rust_rocksdb_cancel_all_background_work_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.cancel_all_background_work(true);
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_cancel_all_background_work_on_drop(false);
+        let db = DB::open(&opts, &path).unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        db.put(b"k2", b"v2").unwrap();
+    }
+    {
+        let db = DB::open_default(&path).unwrap();
+        assert_eq!(db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn live_files_test() {
+    let path = DBPath::new("_rust_rocksdb_live_files_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        assert!(db.live_files().unwrap().is_empty());
+
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+        db.delete_cf(cf1, b"k3").unwrap();
+        db.flush_cf(cf1).unwrap();
+
+        let files = db.live_files().unwrap();
+        assert_eq!(files.len(), 1);
+        let file = &files[0];
+        assert_eq!(file.column_family, "cf1");
+        assert_eq!(file.level, 0);
+        assert!(file.size > 0);
+        assert_eq!(file.smallest_key, Some(b"k1".to_vec()));
+        assert_eq!(file.largest_key, Some(b"k3".to_vec()));
+        assert_eq!(file.num_entries, 3);
+        assert_eq!(file.num_deletions, 1);
+    }
+}
+
+#[test]
+fn delete_file_in_range_test() {
+    let path = DBPath::new("_rust_rocksdb_delete_file_in_range_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        let mut compact_opts = CompactOptions::default();
+        compact_opts.set_change_level(true);
+        compact_opts.set_target_level(1);
+
+        // Write two partitions into separate files, both moved out of L0.
+        for partition in &["a", "b"] {
+            for i in 0..10 {
+                db.put(format!("{}{}", partition, i), b"value").unwrap();
+            }
+            db.flush().unwrap();
+            db.compact_range_opt(
+                Some(partition.to_string()),
+                Some(format!("{}~", partition)),
+                &compact_opts,
+            );
+        }
+        assert_eq!(db.live_files().unwrap().len(), 2);
+
+        db.delete_file_in_range(b"a", b"b").unwrap();
+
+        assert_eq!(db.live_files().unwrap().len(), 1);
+        assert!(db.get(b"a0").unwrap().is_none());
+        assert!(db.get(b"b0").unwrap().is_some());
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::event_listener::{CompactionJobInfo, EventListener, FlushJobInfo};
+use rocksdb::{Options, DB};
+use std::sync::{Arc, Mutex};
+use util::DBPath;
+
+#[derive(Default)]
+struct Events {
+    flushes: Vec<FlushJobInfo>,
+    compactions: Vec<CompactionJobInfo>,
+}
+
+struct Recorder(Arc<Mutex<Events>>);
+
+impl EventListener for Recorder {
+    fn on_flush_completed(&self, info: &FlushJobInfo) {
+        self.0.lock().unwrap().flushes.push(info.clone());
+    }
+
+    fn on_compaction_completed(&self, info: &CompactionJobInfo) {
+        self.0.lock().unwrap().compactions.push(info.clone());
+    }
+}
+
+#[test]
+fn test_event_listener() {
+    let path = DBPath::new("_rust_rocksdb_test_event_listener");
+    let events = Arc::new(Mutex::new(Events::default()));
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.add_event_listener(Recorder(events.clone()));
+
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+
+    // The listener is dropped together with the database.
+    assert_eq!(Arc::strong_count(&events), 1);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.flushes.len(), 2);
+    assert_eq!(events.flushes[0].cf_name, "default");
+    assert!(events.flushes[0].file_path.ends_with(".sst"));
+    assert!(!events.compactions.is_empty());
+    assert!(events.compactions.iter().all(|c| c.status.is_none()));
+}
@@ -14,7 +14,7 @@
 
 mod util;
 
-use rocksdb::{Direction, IteratorMode, MemtableFactory, Options, DB};
+use rocksdb::{Direction, IteratorMode, MemtableFactory, Options, ReadOptions, DB};
 use util::DBPath;
 
 fn cba(input: &[u8]) -> Box<[u8]> {
@@ -207,6 +207,96 @@ fn test_prefix_iterator() {
     }
 }
 
+#[test]
+fn test_tailing_iterator_refresh() {
+    let n = DBPath::new("_rust_rocksdb_tailing_iterator_refresh_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut opts = ReadOptions::default();
+        opts.set_tailing(true);
+        let mut iter = db.iterator_opt(IteratorMode::Start, opts);
+        assert_eq!(iter.next(), Some((key(b"k1"), key(b"v1"))));
+        assert_eq!(iter.next(), None);
+
+        // nothing new yet
+        iter.refresh().unwrap();
+        assert_eq!(iter.next(), None);
+
+        db.put(b"k2", b"v2").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+        db.flush().unwrap();
+        iter.refresh().unwrap();
+        let keys: Vec<_> = (&mut iter).map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![key(b"k2"), key(b"k3")]);
+
+        // the last returned key is gone, but the iterator still resumes after it
+        db.delete(b"k3").unwrap();
+        db.put(b"k4", b"v4").unwrap();
+        iter.refresh().unwrap();
+        assert_eq!(iter.next(), Some((key(b"k4"), key(b"v4"))));
+        assert_eq!(iter.next(), None);
+
+        // only tailing iterators keep track of where they stopped
+        assert!(db.iterator(IteratorMode::Start).refresh().is_err());
+    }
+}
+
+#[test]
+fn test_raw_iterator_refresh() {
+    let n = DBPath::new("_rust_rocksdb_raw_iterator_refresh_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut iter = db.raw_iterator();
+        db.put(b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+        db.delete(b"k1").unwrap();
+
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(&b"k1"[..]));
+
+        iter.refresh().unwrap();
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(&b"k2"[..]));
+        iter.next();
+        assert!(!iter.valid());
+
+        // iterators reading from a snapshot can't be refreshed
+        let snapshot = db.snapshot();
+        let mut iter = snapshot.raw_iterator();
+        assert!(iter.refresh().is_err());
+    }
+}
+
+#[test]
+fn test_prefix_iterator_rev() {
+    let n = DBPath::new("_rust_rocksdb_prefix_iterator_rev_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(3));
+        let db = DB::open(&opts, &n).unwrap();
+
+        for k in &[&b"aaa1"[..], b"aaa2", b"aab1", b"bbb1", b"\xff\xff1"] {
+            db.put(k, k).unwrap();
+        }
+
+        let keys: Vec<_> = db.prefix_iterator_rev(b"aaa").keys().collect();
+        assert_eq!(keys, vec![key(b"aaa2"), key(b"aaa1")]);
+
+        // the latest record under a prefix
+        let (k, _) = db.prefix_iterator_rev(b"aa").next().unwrap();
+        assert_eq!(k, key(b"aab1"));
+
+        let keys: Vec<_> = db.prefix_iterator_rev(b"\xff\xff").keys().collect();
+        assert_eq!(keys, vec![key(b"\xff\xff1")]);
+        assert!(db.prefix_iterator_rev(b"ccc").next().is_none());
+    }
+}
+
 #[test]
 fn test_prefix_iterator_uses_full_prefix() {
     // Test scenario derived from GitHub issue #221
@@ -298,6 +388,30 @@ fn test_full_iterator() {
     }
 }
 
+#[test]
+fn test_keys_iterator() {
+    let path = DBPath::new("_rust_rocksdb_keys_iterator_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+
+        let keys: Vec<_> = db.keys_iterator(IteratorMode::Start).collect();
+        assert_eq!(keys, vec![cba(b"k1"), cba(b"k2"), cba(b"k3")]);
+
+        let keys: Vec<_> = db
+            .iterator(IteratorMode::From(b"k2", Direction::Reverse))
+            .keys()
+            .collect();
+        assert_eq!(keys, vec![cba(b"k2"), cba(b"k1")]);
+
+        let mut iter = db.keys_iterator(IteratorMode::End);
+        assert_eq!(iter.by_ref().count(), 3);
+        assert!(iter.status().is_ok());
+    }
+}
+
 fn custom_iter<'a>(db: &'a DB) -> impl Iterator<Item = usize> + 'a {
     db.iterator(IteratorMode::Start)
         .map(|(_, db_value)| db_value.len())
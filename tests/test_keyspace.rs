@@ -0,0 +1,94 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::{Direction, IteratorMode, Keyspace, Options, WriteBatch, DB};
+use util::DBPath;
+
+fn keys(iter: impl Iterator<Item = (Box<[u8]>, Box<[u8]>)>) -> Vec<Vec<u8>> {
+    iter.map(|(k, _)| k.to_vec()).collect()
+}
+
+#[test]
+fn test_keyspace() {
+    let path = DBPath::new("_rust_rocksdb_test_keyspace");
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"a", b"outside").unwrap();
+    db.put(b"t2", b"outside").unwrap();
+
+    let ks = Keyspace::new(&db, b"t1");
+    assert_eq!(ks.prefix(), b"t1");
+    ks.put(b"b", b"2").unwrap();
+    ks.put(b"a", b"1").unwrap();
+    ks.put(b"c", b"3").unwrap();
+
+    assert_eq!(ks.get(b"a").unwrap().unwrap(), b"1");
+    assert_eq!(db.get(b"t1a").unwrap().unwrap(), b"1");
+    assert!(ks.get(b"t2").unwrap().is_none());
+
+    assert_eq!(
+        keys(ks.iterator(IteratorMode::Start)),
+        vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+    );
+    assert_eq!(
+        keys(ks.iterator(IteratorMode::End)),
+        vec![b"c".to_vec(), b"b".to_vec(), b"a".to_vec()]
+    );
+    assert_eq!(
+        keys(ks.iterator(IteratorMode::From(b"b", Direction::Forward))),
+        vec![b"b".to_vec(), b"c".to_vec()]
+    );
+    assert_eq!(
+        keys(ks.iterator(IteratorMode::From(b"bb", Direction::Reverse))),
+        vec![b"b".to_vec(), b"a".to_vec()]
+    );
+
+    ks.delete(b"b").unwrap();
+    assert!(ks.get(b"b").unwrap().is_none());
+    assert_eq!(
+        keys(ks.iterator(IteratorMode::Start)),
+        vec![b"a".to_vec(), b"c".to_vec()]
+    );
+    assert_eq!(db.get(b"t2").unwrap().unwrap(), b"outside");
+}
+
+#[test]
+fn test_keyspace_cf_and_batch() {
+    let path = DBPath::new("_rust_rocksdb_test_keyspace_cf_and_batch");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, &["cf"]).unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+
+    let users = Keyspace::new_cf(&db, cf, b"u/");
+    let orders = Keyspace::new_cf(&db, cf, b"o/");
+    let mut batch = WriteBatch::default();
+    users.batch_put(&mut batch, b"1", b"alice");
+    users.batch_put(&mut batch, b"12", b"bob");
+    users.batch_put(&mut batch, b"2", b"carol");
+    orders.batch_put(&mut batch, b"1", b"book");
+    orders.batch_delete(&mut batch, b"1");
+    db.write(batch).unwrap();
+
+    assert!(db.get(b"u/1").unwrap().is_none());
+    assert_eq!(db.get_cf(cf, b"u/1").unwrap().unwrap(), b"alice");
+    assert!(orders.get(b"1").unwrap().is_none());
+    assert_eq!(
+        keys(users.prefix_iterator(b"1")),
+        vec![b"1".to_vec(), b"12".to_vec()]
+    );
+    assert_eq!(orders.iterator(IteratorMode::Start).count(), 0);
+}
@@ -0,0 +1,109 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::logger::Logger;
+use rocksdb::{LogLevel, Options, DB};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use util::DBPath;
+
+struct Recorder(Arc<Mutex<Vec<(LogLevel, String)>>>);
+
+impl Logger for Recorder {
+    fn log(&self, level: LogLevel, message: &str) {
+        self.0.lock().unwrap().push((level, message.to_owned()));
+    }
+}
+
+#[test]
+fn test_logger() {
+    let path = DBPath::new("_rust_rocksdb_test_logger");
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_logger(LogLevel::Info, Recorder(lines.clone()));
+
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+
+    // The logger is dropped together with the database and options.
+    assert_eq!(Arc::strong_count(&lines), 1);
+
+    let lines = lines.lock().unwrap();
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|(level, _)| *level >= LogLevel::Info));
+    assert!(lines.iter().any(|(_, msg)| msg.contains("RocksDB version")));
+    // nothing is written to the LOG file
+    assert!(fs::read_dir(&path)
+        .unwrap()
+        .all(|entry| entry.unwrap().file_name() != "LOG"));
+}
+
+#[test]
+fn test_stats_dump_to_logger() {
+    let path = DBPath::new("_rust_rocksdb_test_stats_dump_to_logger");
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        opts.set_stats_dump_period_sec(1);
+        opts.set_stats_persist_period_sec(1);
+        opts.set_logger(LogLevel::Info, Recorder(lines.clone()));
+
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let has_stats = || {
+            lines
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|(_, msg)| msg.contains("** DB Stats **"))
+        };
+        for _ in 0..100 {
+            if has_stats() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        assert!(has_stats());
+    }
+}
+
+#[test]
+fn test_db_log_dir() {
+    let path = DBPath::new("_rust_rocksdb_test_db_log_dir");
+    let log_dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_test_db_log_dir_logs")
+        .tempdir()
+        .unwrap();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_log_level(LogLevel::Warn);
+        opts.set_db_log_dir(log_dir.path());
+        opts.set_keep_log_file_num(2);
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    assert!(fs::read_dir(log_dir.path()).unwrap().next().is_some());
+}
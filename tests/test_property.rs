@@ -14,7 +14,7 @@
 
 mod util;
 
-use rocksdb::{Options, DB};
+use rocksdb::{properties, Options, DB};
 use util::DBPath;
 
 #[test]
@@ -33,7 +33,7 @@ fn property_cf_test() {
     let n = DBPath::new("_rust_rocksdb_property_cf_test");
     {
         let opts = Options::default();
-        let mut db = DB::open_default(&n).unwrap();
+        let db = DB::open_default(&n).unwrap();
         db.create_cf("cf1", &opts).unwrap();
         let cf = db.cf_handle("cf1").unwrap();
         let value = db.property_value_cf(cf, "rocksdb.stats").unwrap().unwrap();
@@ -60,7 +60,7 @@ fn property_int_cf_test() {
     let n = DBPath::new("_rust_rocksdb_property_int_cf_test");
     {
         let opts = Options::default();
-        let mut db = DB::open_default(&n).unwrap();
+        let db = DB::open_default(&n).unwrap();
         db.create_cf("cf1", &opts).unwrap();
         let cf = db.cf_handle("cf1").unwrap();
         let total_keys = db
@@ -70,3 +70,27 @@ fn property_int_cf_test() {
         assert_eq!(total_keys, Some(0));
     }
 }
+
+#[test]
+fn property_int_monitoring_test() {
+    let n = DBPath::new("_rust_rocksdb_property_int_monitoring_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let num_keys = db
+            .property_int_value(properties::ESTIMATE_NUM_KEYS)
+            .unwrap();
+        assert_eq!(num_keys, Some(1));
+
+        let mem_size = db
+            .property_int_value(properties::CUR_SIZE_ALL_MEM_TABLES)
+            .unwrap();
+        assert!(mem_size.unwrap() > 0);
+
+        let live_size = db
+            .property_int_value(properties::ESTIMATE_LIVE_DATA_SIZE)
+            .unwrap();
+        assert_eq!(live_size, Some(0));
+    }
+}
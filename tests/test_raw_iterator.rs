@@ -14,7 +14,7 @@
 
 mod util;
 
-use rocksdb::DB;
+use rocksdb::{IterBatch, DB};
 use util::DBPath;
 
 #[test]
@@ -150,3 +150,43 @@ pub fn test_seek_for_prev() {
         assert_eq!(iter.value(), Some(b"v2".as_ref()));
     }
 }
+
+#[test]
+pub fn test_next_batch() {
+    let n = DBPath::new("next_batch");
+    {
+        let db = DB::open_default(&n).unwrap();
+        for i in 0..10 {
+            db.put(format!("k{}", i), format!("v{}", i)).unwrap();
+        }
+
+        let mut iter = db.raw_iterator();
+        iter.seek_to_first();
+        let mut batch = IterBatch::new();
+        let mut keys = vec![];
+        let mut sizes = vec![];
+        loop {
+            let read = iter.next_batch(&mut batch, 4);
+            if read == 0 {
+                break;
+            }
+            sizes.push(read);
+            assert_eq!(batch.len(), read);
+            for (key, value) in batch.iter() {
+                assert_eq!(&key[1..], &value[1..]);
+                keys.push(key.to_vec());
+            }
+        }
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert_eq!(keys.len(), 10);
+        assert_eq!(keys[0], b"k0");
+        assert_eq!(keys[9], b"k9");
+        assert!(!iter.valid());
+
+        iter.seek(b"k2");
+        assert_eq!(iter.prev_batch(&mut batch, 5), 3);
+        assert_eq!(batch.get(0), Some((&b"k2"[..], &b"v2"[..])));
+        assert_eq!(batch.get(2), Some((&b"k0"[..], &b"v0"[..])));
+        assert!(batch.get(3).is_none());
+    }
+}
@@ -14,7 +14,11 @@
 
 mod util;
 
-use rocksdb::{BlockBasedOptions, DataBlockIndexType, Options, ReadOptions, DB};
+use rocksdb::{
+    BlockBasedIndexType, BlockBasedOptions, Cache, CuckooTableOptions, DBCompactionStyle,
+    DBCompressionType, DataBlockIndexType, FifoCompactOptions, Options, ReadOptions,
+    UniversalCompactOptions, UniversalCompactionStopStyle, WriteBufferManager, DB,
+};
 use std::{fs, io::Read as _};
 use util::DBPath;
 
@@ -64,6 +68,7 @@ fn test_block_based_options() {
         block_opts.set_pin_l0_filter_and_index_blocks_in_cache(true);
         block_opts.set_format_version(4);
         block_opts.set_index_block_restart_interval(16);
+        block_opts.set_whole_key_filtering(false);
 
         opts.set_block_based_table_factory(&block_opts);
         let _db = DB::open(&opts, &n).unwrap();
@@ -79,6 +84,7 @@ fn test_block_based_options() {
         assert!(settings.contains("pin_l0_filter_and_index_blocks_in_cache: 1"));
         assert!(settings.contains("format_version: 4"));
         assert!(settings.contains("index_block_restart_interval: 16"));
+        assert!(settings.contains("whole_key_filtering: 0"));
     }
 }
 
@@ -133,3 +139,373 @@ fn test_set_data_block_index_type() {
         assert!(settings.contains("data_block_hash_table_util_ratio: 0.350000"));
     }
 }
+
+#[test]
+fn test_shared_block_cache() {
+    let n1 = DBPath::new("_rust_rocksdb_test_shared_block_cache_1");
+    let n2 = DBPath::new("_rust_rocksdb_test_shared_block_cache_2");
+    {
+        let cache = Cache::new_lru_cache(1 << 20);
+
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(&cache);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+
+        let db1 = DB::open(&opts, &n1).unwrap();
+        let db2 = DB::open(&opts, &n2).unwrap();
+
+        for db in &[&db1, &db2] {
+            db.put(b"k1", b"v1").unwrap();
+            db.flush().unwrap();
+            assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        }
+
+        // Both databases fill the same cache.
+        assert!(cache.get_usage() > 0);
+    }
+}
+
+#[test]
+fn test_write_buffer_manager() {
+    let n1 = DBPath::new("_rust_rocksdb_test_write_buffer_manager_1");
+    let n2 = DBPath::new("_rust_rocksdb_test_write_buffer_manager_2");
+    {
+        let cache = Cache::new_lru_cache(16 << 20);
+        let manager = WriteBufferManager::new_with_cache(4 << 20, &cache);
+        assert_eq!(manager.get_buffer_size(), 4 << 20);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_write_buffer_manager(&manager);
+
+        let db1 = DB::open(&opts, &n1).unwrap();
+        let db2 = DB::open(&opts, &n2).unwrap();
+        for db in &[&db1, &db2] {
+            db.put(b"k1", vec![0; 1024]).unwrap();
+        }
+
+        // The memtables of both databases are accounted to the manager.
+        assert!(manager.get_usage() > 0);
+    }
+}
+
+#[test]
+fn test_universal_and_fifo_compaction() {
+    let universal = DBPath::new("_rust_rocksdb_test_universal_compaction");
+    let fifo = DBPath::new("_rust_rocksdb_test_fifo_compaction");
+    {
+        let mut uco = UniversalCompactOptions::default();
+        uco.set_size_ratio(2);
+        uco.set_min_merge_width(2);
+        uco.set_max_merge_width(8);
+        uco.set_max_size_amplification_percent(100);
+        uco.set_compression_size_percent(-1);
+        uco.set_stop_style(UniversalCompactionStopStyle::Similar);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::Universal);
+        opts.set_universal_compaction_options(&uco);
+
+        let db = DB::open(&opts, &universal).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+    {
+        let mut fco = FifoCompactOptions::default();
+        fco.set_max_table_files_size(1 << 20);
+        fco.set_allow_compaction(true);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::Fifo);
+        opts.set_fifo_compaction_options(&fco);
+
+        let db = DB::open(&opts, &fifo).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+#[cfg(all(feature = "lz4", feature = "zstd"))]
+fn test_bottommost_compression() {
+    let n = DBPath::new("_rust_rocksdb_test_bottommost_compression");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_per_level(&[
+            DBCompressionType::None,
+            DBCompressionType::Lz4,
+            DBCompressionType::Lz4,
+        ]);
+        opts.set_zstd_max_train_bytes(0);
+        opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+        opts.set_bottommost_compression_options(-14, 3, 0, 16 * 1024);
+        opts.set_bottommost_zstd_max_train_bytes(100 * 16 * 1024);
+
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{}", i), b"value").unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(db.get(b"key1").unwrap().unwrap(), b"value");
+    }
+}
+
+#[test]
+fn test_wal_options() {
+    let n = DBPath::new("_rust_rocksdb_test_wal_options");
+    let wal_dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_test_wal_options_wal")
+        .tempdir()
+        .unwrap();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_wal_dir(wal_dir.path());
+        opts.set_wal_recovery_mode(rocksdb::DBRecoveryMode::TolerateCorruptedTailRecords);
+        opts.set_max_total_wal_size(1 << 30);
+        opts.set_wal_ttl_seconds(60);
+        opts.set_wal_size_limit_mb(16);
+        opts.set_manifest_preallocation_size(1 << 20);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    let has_wal = fs::read_dir(wal_dir.path())
+        .unwrap()
+        .any(|entry| entry.unwrap().path().extension() == Some("log".as_ref()));
+    assert!(has_wal);
+}
+
+#[test]
+fn test_background_job_options() {
+    let n = DBPath::new("_rust_rocksdb_test_background_job_options");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_max_background_jobs(4);
+        opts.set_max_subcompactions(2);
+        opts.set_compaction_readahead_size(2 << 20);
+        opts.set_bytes_per_sync(1 << 20);
+        opts.set_wal_bytes_per_sync(1 << 20);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), b"value").unwrap();
+        }
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(db.iterator(rocksdb::IteratorMode::Start).count(), 100);
+    }
+}
+
+#[test]
+fn test_level_shape_options() {
+    let n = DBPath::new("_rust_rocksdb_test_level_shape_options");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_num_levels(5);
+        opts.set_level_compaction_dynamic_level_bytes(true);
+        opts.set_max_bytes_for_level_base(64 << 20);
+        opts.set_max_bytes_for_level_multiplier(8.0);
+        opts.set_target_file_size_base(8 << 20);
+        opts.set_target_file_size_multiplier(2);
+        opts.set_level_zero_file_num_compaction_trigger(8);
+        opts.set_level_zero_slowdown_writes_trigger(32);
+        opts.set_level_zero_stop_writes_trigger(64);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_memtable_bloom_options() {
+    let n = DBPath::new("_rust_rocksdb_test_memtable_bloom_options");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(2));
+        opts.set_memtable_prefix_bloom_ratio(0.1);
+        opts.set_memtable_whole_key_filtering(true);
+        opts.set_max_write_buffer_number(4);
+        opts.set_min_write_buffer_number_to_merge(2);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.get(b"k2").unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_partitioned_index_and_filters() {
+    let n = DBPath::new("_rust_rocksdb_test_partitioned_index_and_filters");
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_index_type(BlockBasedIndexType::TwoLevelIndexSearch);
+        block_opts.set_bloom_filter(10, false);
+        block_opts.set_partition_filters(true);
+        block_opts.set_metadata_block_size(256);
+        block_opts.set_cache_index_and_filter_blocks(true);
+        block_opts.set_pin_top_level_index_and_filter(true);
+        block_opts.set_block_size(256);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), b"value").unwrap();
+        }
+        db.flush().unwrap();
+        assert_eq!(db.get(500u32.to_be_bytes()).unwrap().unwrap(), b"value");
+        assert!(db.get(1000u32.to_be_bytes()).unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_row_cache() {
+    let n = DBPath::new("_rust_rocksdb_test_row_cache");
+    {
+        let cache = Cache::new_lru_cache(1 << 20);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_row_cache(&cache);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(cache.get_usage(), 0);
+
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(cache.get_usage() > 0);
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_cuckoo_table_factory() {
+    let n = DBPath::new("_rust_rocksdb_test_cuckoo_table_factory");
+    {
+        let mut factory_opts = CuckooTableOptions::default();
+        factory_opts.set_hash_ratio(0.8);
+        factory_opts.set_cuckoo_block_size(4);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        // cuckoo tables are read through mmap and need fixed size keys and values
+        opts.set_allow_mmap_reads(true);
+        opts.set_cuckoo_table_factory(&factory_opts);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), (i * 2).to_be_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        assert_eq!(
+            db.get(7u32.to_be_bytes()).unwrap().unwrap(),
+            14u32.to_be_bytes()
+        );
+        assert!(db.get(100u32.to_be_bytes()).unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_file_handle_options() {
+    let n = DBPath::new("_rust_rocksdb_test_file_handle_options");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.increase_parallelism(4);
+        opts.set_max_open_files(-1);
+        opts.set_max_file_opening_threads(4);
+        opts.set_table_cache_num_shard_bits(4);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_max_open_files(-1);
+        opts.set_max_file_opening_threads(4);
+        let db = DB::open(&opts, &n).unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_mmap_and_direct_io_are_exclusive() {
+    let n = DBPath::new("_rust_rocksdb_test_mmap_and_direct_io_are_exclusive");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_allow_mmap_reads(true);
+        opts.set_use_direct_reads(true);
+        assert!(DB::open(&opts, &n).is_err());
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_allow_mmap_writes(true);
+        opts.set_use_direct_io_for_flush_and_compaction(true);
+        assert!(DB::open(&opts, &n).is_err());
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_allow_mmap_reads(true);
+        opts.set_allow_mmap_writes(true);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_fast_open_options() {
+    let n = DBPath::new("_rust_rocksdb_test_fast_open_options");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_use_fsync(true);
+        opts.set_paranoid_checks(true);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_paranoid_checks(false);
+        opts.set_skip_checking_sst_file_sizes_on_db_open(true);
+        opts.set_skip_stats_update_on_db_open(true);
+        let db = DB::open(&opts, &n).unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_set_db_paths() {
+    let n = DBPath::new("_rust_rocksdb_test_set_db_paths");
+    let sst_dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_test_set_db_paths_sst")
+        .tempdir()
+        .unwrap();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_db_paths(&[rocksdb::DBPath::new(sst_dir.path(), 1 << 30)]);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+    let has_sst = fs::read_dir(sst_dir.path())
+        .unwrap()
+        .any(|entry| entry.unwrap().path().extension() == Some("sst".as_ref()));
+    assert!(has_sst);
+}
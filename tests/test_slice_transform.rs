@@ -14,7 +14,7 @@
 
 mod util;
 
-use rocksdb::{Options, SliceTransform, DB};
+use rocksdb::{Direction, IteratorMode, Options, ReadOptions, SliceTransform, DB};
 use util::DBPath;
 
 #[test]
@@ -64,3 +64,33 @@ pub fn test_slice_transform() {
         }
     }
 }
+
+#[test]
+pub fn test_fixed_prefix_seek() {
+    let db_path = DBPath::new("_rust_rocksdb_fixed_prefix_seek_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+        opts.set_memtable_prefix_bloom_ratio(0.1);
+
+        let db = DB::open(&opts, &db_path).unwrap();
+        for key in &[b"aaa1", b"aaa2", b"bbb1", b"bbb2"] {
+            assert!(db.put(key, key).is_ok());
+        }
+
+        let keys = |readopts: ReadOptions| -> Vec<Vec<u8>> {
+            db.iterator_opt(IteratorMode::From(b"aaa", Direction::Forward), readopts)
+                .map(|(k, _)| k.to_vec())
+                .collect()
+        };
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_prefix_same_as_start(true);
+        assert_eq!(keys(readopts), vec![b"aaa1".to_vec(), b"aaa2".to_vec()]);
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_total_order_seek(true);
+        assert_eq!(keys(readopts).len(), 4);
+    }
+}
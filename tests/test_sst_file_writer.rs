@@ -14,7 +14,7 @@
 
 mod util;
 
-use rocksdb::{Error, Options, SstFileWriter, DB};
+use rocksdb::{Error, IngestExternalFileOptions, Options, SstFileWriter, DB};
 
 use util::DBPath;
 
@@ -49,3 +49,38 @@ fn sst_file_writer_works() {
         assert!(db.get(b"k3").unwrap().is_none());
     }
 }
+
+#[test]
+fn sst_file_writer_ingest_cf() {
+    let db_path = DBPath::new("_rust_rocksdb_sstfilewriter_ingest_cf_test");
+    let dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_sstfilewriter_ingest_cf_test")
+        .tempdir()
+        .expect("Failed to create temporary path for file writer.");
+    let writer_path = dir.path().join("filewriter");
+    {
+        let opts = Options::default();
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(&writer_path).unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.finish().unwrap();
+    }
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &db_path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let mut ingest_opts = IngestExternalFileOptions::default();
+        ingest_opts.set_move_files(true);
+        db.ingest_external_file_cf_opts(cf1, &ingest_opts, vec![&writer_path])
+            .unwrap();
+
+        assert_eq!(db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get_cf(cf1, b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(!writer_path.exists());
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "test-utils")]
+
+use rocksdb::{test_utils::TempDBPath, DB};
+
+#[test]
+fn test_temp_db_path() {
+    let first = TempDBPath::new("_rust_rocksdb_test_utils");
+    let second = TempDBPath::new("_rust_rocksdb_test_utils");
+    assert_ne!(first.path(), second.path());
+    assert!(!first.path().exists());
+
+    let dir = first.path().parent().unwrap().to_path_buf();
+    {
+        let db = DB::open_default(&first).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    assert!(first.path().exists());
+    drop(first);
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_temp_db_path_drop_while_panicking() {
+    let result = std::panic::catch_unwind(|| {
+        let path = TempDBPath::new("_rust_rocksdb_test_utils_panicking");
+        let db = DB::open_default(&path).unwrap();
+        // The path is dropped first, while the database still holds its lock.
+        let _open = (path, db);
+        panic!("test failure");
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,92 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::typed::{RawCodec, TypedDb};
+use rocksdb::{Direction, DynKvStore, IteratorMode, DB};
+use std::sync::Arc;
+use util::DBPath;
+
+#[test]
+fn test_typed_db() {
+    let path = DBPath::new("_rust_rocksdb_test_typed_db");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+    let counters: TypedDb<_, u32, u64, RawCodec> = TypedDb::new(db.clone());
+
+    for i in (0..300u32).rev() {
+        counters.put(&i, &(u64::from(i) * 10)).unwrap();
+    }
+    assert_eq!(counters.get(&7).unwrap(), Some(70));
+    assert_eq!(counters.get(&300).unwrap(), None);
+
+    // keys are iterated in numeric order
+    let keys: Vec<u32> = counters
+        .iterator(IteratorMode::Start)
+        .map(|entry| entry.unwrap().0)
+        .collect();
+    assert_eq!(keys, (0..300).collect::<Vec<_>>());
+
+    let mut iter = counters.iterator_from(&255, Direction::Reverse).unwrap();
+    assert_eq!(iter.next().unwrap().unwrap(), (255, 2550));
+    assert_eq!(iter.next().unwrap().unwrap(), (254, 2540));
+
+    counters.delete(&7).unwrap();
+    assert_eq!(counters.get(&7).unwrap(), None);
+
+    // reads through a snapshot
+    let snapshot = db.snapshot();
+    counters.put(&7, &1).unwrap();
+    let snapshot_counters: TypedDb<_, u32, u64, RawCodec> = TypedDb::new(&snapshot);
+    assert_eq!(snapshot_counters.get(&7).unwrap(), None);
+
+    // values of the wrong size fail to decode
+    db.put(8u32.to_be_bytes(), b"short").unwrap();
+    assert!(counters.get(&8).is_err());
+}
+
+#[test]
+fn test_typed_db_over_trait_object() {
+    let path = DBPath::new("_rust_rocksdb_test_typed_db_over_trait_object");
+    let db = DB::open_default(&path).unwrap();
+    let store: &dyn DynKvStore = &db;
+    let names: TypedDb<_, String, String, RawCodec> = TypedDb::new(store);
+    names.put(&"k1".to_owned(), &"v1".to_owned()).unwrap();
+    assert_eq!(names.get(&"k1".to_owned()).unwrap(), Some("v1".to_owned()));
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+}
+
+#[cfg(feature = "serde-bincode")]
+#[test]
+fn test_typed_db_bincode() {
+    use rocksdb::typed::BincodeCodec;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+    }
+
+    let path = DBPath::new("_rust_rocksdb_test_typed_db_bincode");
+    let db = DB::open_default(&path).unwrap();
+    let users: TypedDb<_, (u32, u32), User, BincodeCodec> = TypedDb::new(&db);
+    let alice = User {
+        name: "alice".to_owned(),
+        age: 30,
+    };
+    users.put(&(1, 2), &alice).unwrap();
+    assert_eq!(users.get(&(1, 2)).unwrap(), Some(alice));
+    assert_eq!(users.get(&(2, 1)).unwrap(), None);
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use rocksdb::WriteBatch;
+use rocksdb::{BatchOp, WriteBatch, WriteBatchIterator};
 
 #[test]
 fn test_write_batch_clear() {
@@ -23,3 +23,91 @@ fn test_write_batch_clear() {
     assert_eq!(batch.len(), 0);
     assert!(batch.is_empty());
 }
+
+struct Collector(Vec<(Vec<u8>, Option<Vec<u8>>)>);
+
+impl WriteBatchIterator for Collector {
+    fn put(&mut self, key: Box<[u8]>, value: Box<[u8]>) {
+        self.0.push((key.into_vec(), Some(value.into_vec())));
+    }
+
+    fn delete(&mut self, key: Box<[u8]>) {
+        self.0.push((key.into_vec(), None));
+    }
+}
+
+#[test]
+fn test_write_batch_from_data() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1");
+    batch.delete(b"k2");
+    assert_eq!(batch.data().len(), batch.size_in_bytes());
+
+    let copy = WriteBatch::from_data(batch.data()).unwrap();
+    assert_eq!(copy.len(), 2);
+    assert_eq!(copy.data(), batch.data());
+    let mut ops = Collector(Vec::new());
+    copy.iterate(&mut ops);
+    assert_eq!(
+        ops.0,
+        vec![
+            (b"k1".to_vec(), Some(b"v1".to_vec())),
+            (b"k2".to_vec(), None)
+        ]
+    );
+
+    assert!(WriteBatch::from_data(b"short").is_err());
+}
+
+#[test]
+fn test_write_batch_ops() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1");
+    batch.delete(b"k2");
+    batch.single_delete(b"k3");
+    batch.merge(b"k4", b"v4");
+    batch.delete_range(b"k5", b"k7");
+
+    let b = |s: &[u8]| s.to_vec().into_boxed_slice();
+    let ops: Vec<_> = batch.ops().unwrap().collect();
+    assert_eq!(
+        ops,
+        vec![
+            BatchOp::Put {
+                cf_id: 0,
+                key: b(b"k1"),
+                value: b(b"v1"),
+            },
+            BatchOp::Delete {
+                cf_id: 0,
+                key: b(b"k2"),
+            },
+            BatchOp::SingleDelete {
+                cf_id: 0,
+                key: b(b"k3"),
+            },
+            BatchOp::Merge {
+                cf_id: 0,
+                key: b(b"k4"),
+                value: b(b"v4"),
+            },
+            BatchOp::DeleteRange {
+                cf_id: 0,
+                from: b(b"k5"),
+                to: b(b"k7"),
+            },
+        ]
+    );
+
+    // the batch isn't consumed
+    assert_eq!(batch.ops().unwrap().count(), 5);
+    assert!(WriteBatch::default().ops().unwrap().next().is_none());
+
+    // truncated batches are reported instead of returning the operations before the end
+    let data = batch.data();
+    let truncated = WriteBatch::from_data(&data[..data.len() - 1]).unwrap();
+    assert!(truncated.ops().is_err());
+    let mut header = [0; 12];
+    header[8] = 1;
+    assert!(WriteBatch::from_data(&header).unwrap().ops().is_err());
+}
@@ -0,0 +1,109 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::{Options, ReadOptions, WriteBatchWithIndex, DB};
+use util::DBPath;
+
+#[test]
+fn test_write_batch_with_index_get() {
+    let path = DBPath::new("_rust_rocksdb_wbwi_get");
+    let opts = Options::default();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"db").unwrap();
+    db.put(b"k2", b"db").unwrap();
+
+    let mut batch = WriteBatchWithIndex::new(0, true);
+    batch.put(b"k1", b"batch");
+    batch.delete(b"k2");
+    batch.put(b"k3", b"batch");
+    assert_eq!(batch.len(), 3);
+
+    assert_eq!(
+        batch.get_from_batch(&opts, b"k1").unwrap().unwrap(),
+        b"batch"
+    );
+    assert!(batch.get_from_batch(&opts, b"k4").unwrap().is_none());
+
+    assert_eq!(
+        batch.get_from_batch_and_db(&db, b"k1").unwrap().unwrap(),
+        b"batch"
+    );
+    assert!(batch.get_from_batch_and_db(&db, b"k2").unwrap().is_none());
+    assert_eq!(
+        batch.get_from_batch_and_db(&db, b"k3").unwrap().unwrap(),
+        b"batch"
+    );
+
+    // nothing is visible in the db until the batch is written
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"db");
+    db.write_wbwi(&batch).unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"batch");
+    assert!(db.get(b"k2").unwrap().is_none());
+}
+
+#[test]
+fn test_write_batch_with_index_save_point() {
+    let mut batch = WriteBatchWithIndex::default();
+    assert!(batch.rollback_to_save_point().is_err());
+    batch.put(b"k1", b"v1");
+    batch.set_save_point();
+    batch.put(b"k2", b"v2");
+    assert_eq!(batch.len(), 2);
+    batch.rollback_to_save_point().unwrap();
+    assert_eq!(batch.len(), 1);
+    batch.clear();
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_write_batch_with_index_iterator() {
+    let path = DBPath::new("_rust_rocksdb_wbwi_iterator");
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"a", b"db").unwrap();
+    db.put(b"b", b"db").unwrap();
+    db.put(b"c", b"db").unwrap();
+
+    let mut batch = WriteBatchWithIndex::new(0, true);
+    batch.delete(b"b");
+    batch.put(b"c", b"batch");
+    batch.put(b"d", b"batch");
+
+    let mut iter = batch.raw_iterator_with_base(&db, ReadOptions::default());
+    iter.seek_to_first();
+    let mut items = vec![];
+    while iter.valid() {
+        items.push((iter.key().unwrap().to_vec(), iter.value().unwrap().to_vec()));
+        iter.next();
+    }
+    assert!(iter.status().is_ok());
+    assert_eq!(
+        items,
+        vec![
+            (b"a".to_vec(), b"db".to_vec()),
+            (b"c".to_vec(), b"batch".to_vec()),
+            (b"d".to_vec(), b"batch".to_vec()),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "requires overwrite_key")]
+fn test_write_batch_with_index_iterator_requires_overwrite_key() {
+    let path = DBPath::new("_rust_rocksdb_wbwi_iterator_no_overwrite_key");
+    let db = DB::open_default(&path).unwrap();
+    let batch = WriteBatchWithIndex::new(0, false);
+    let _iter = batch.raw_iterator_with_base(&db, ReadOptions::default());
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod util;
+
+use rocksdb::{WriteCoalescer, DB};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use util::DBPath;
+
+#[test]
+fn test_write_coalescer_many_threads() {
+    let path = DBPath::new("_rust_rocksdb_test_write_coalescer_many_threads");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+    db.put(b"deleted", b"v").unwrap();
+    {
+        let coalescer = Arc::new(WriteCoalescer::new(
+            db.clone(),
+            16,
+            Duration::from_millis(2),
+        ));
+        assert!(Arc::ptr_eq(coalescer.db(), &db));
+
+        let writers: Vec<_> = (0..8)
+            .map(|t| {
+                let coalescer = coalescer.clone();
+                thread::spawn(move || {
+                    for i in 0..50 {
+                        let key = format!("t{}k{}", t, i);
+                        coalescer.put(&key, &key).unwrap();
+                    }
+                })
+            })
+            .collect();
+        coalescer.delete(b"deleted").unwrap();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+    }
+
+    assert!(db.get(b"deleted").unwrap().is_none());
+    for t in 0..8 {
+        for i in 0..50 {
+            let key = format!("t{}k{}", t, i);
+            assert_eq!(db.get(&key).unwrap().unwrap(), key.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn test_write_coalescer_flushes_full_batches() {
+    let path = DBPath::new("_rust_rocksdb_test_write_coalescer_full_batches");
+    let db = Arc::new(DB::open_default(&path).unwrap());
+    let coalescer = Arc::new(WriteCoalescer::new(db.clone(), 4, Duration::from_secs(60)));
+    let seq = db.latest_sequence_number();
+
+    // Four writes fill a batch, so none of them waits for the delay.
+    let start = Instant::now();
+    let writers: Vec<_> = (0..4u8)
+        .map(|i| {
+            let coalescer = coalescer.clone();
+            thread::spawn(move || coalescer.put([i], [i]).unwrap())
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    assert!(start.elapsed() < Duration::from_secs(30));
+    assert_eq!(db.latest_sequence_number(), seq + 4);
+    for i in 0..4u8 {
+        assert_eq!(db.get([i]).unwrap().unwrap(), [i]);
+    }
+}